@@ -0,0 +1,208 @@
+// An assembler for the duet language. On top of the plain "op x y" lines it
+// takes comments, labels, named constants and extra registers:
+//
+//   # Counts down from N
+//   const N = 10
+//   reg counter
+//           set counter N
+//   loop:   sub counter 1
+//           jnz counter loop
+//
+// Comments run from # to the end of the line. A label names the position of
+// the next instruction and can be used as the offset of a jump, which then
+// goes to the label. Constants are numbers or earlier constants. reg adds
+// registers after those of the instruction set. Labels, constants and
+// registers share one namespace.
+
+use std::collections::HashMap;
+
+use duet::Instruction;
+use duet::InstructionSet;
+use duet::Opcode;
+use duet::Operand;
+use Error;
+
+// An assembled program. set is the instruction set it was assembled for,
+// with the declared registers added.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Program {
+    pub set: InstructionSet,
+    pub code: Vec<Instruction>,
+    // The labels and where they point to, in the order they were defined
+    pub labels: Vec<(String, usize)>,
+    // The source line of every instruction, counted from 1
+    pub lines: Vec<usize>,
+}
+
+impl Program {
+    // The labels pointing to pc
+    pub fn labels_at(&self, pc: usize) -> impl Iterator<Item = &str> + '_ {
+        self.labels.iter().filter(move |l| l.1 == pc).map(|l| l.0.as_str())
+    }
+
+    // The program as plain instructions, one per line
+    pub fn listing(&self) -> String {
+        self.code.iter().map(|ins| self.set.format(ins) + "\n").collect()
+    }
+}
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+enum Symbol {
+    Label(usize),
+    Const(i64),
+    Reg(usize),
+}
+
+fn is_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => (),
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// The part of the line before any comment
+fn code(line: &str) -> &str {
+    line.split('#').next().unwrap()
+}
+
+// Splits off the labels in front of an instruction
+fn labels(line: &str) -> (Vec<&str>, Vec<&str>) {
+    let tokens: Vec<_> = code(line).split_whitespace().collect();
+    let n = tokens.iter().take_while(|t| t.ends_with(':')).count();
+    let labels = tokens[..n].iter().map(|t| &t[..t.len() - 1]).collect();
+    (labels, tokens[n..].to_vec())
+}
+
+struct Assembler {
+    set: InstructionSet,
+    symbols: HashMap<String, Symbol>,
+    labels: Vec<(String, usize)>,
+}
+
+impl Assembler {
+    fn define(&mut self, line: &str, name: &str, symbol: Symbol) -> Result<(), Error> {
+        if !is_name(name) {
+            return Err(Error::token(line, name, "Invalid name"));
+        }
+        if self.symbols.contains_key(name) {
+            return Err(Error::token(line, name, "Defined twice"));
+        }
+        self.symbols.insert(name.to_string(), symbol);
+        Ok(())
+    }
+
+    fn constant(&self, line: &str, token: &str) -> Result<i64, Error> {
+        if let Ok(n) = token.parse::<i64>() {
+            return Ok(n);
+        }
+        match self.symbols.get(token) {
+            Some(&Symbol::Const(n)) => Ok(n),
+            _ => Err(Error::token(line, token, "Expected a number or constant")),
+        }
+    }
+
+    // Collects the labels, constants and registers, returns the number of
+    // instructions
+    fn declare(&mut self, line: &str, pc: usize) -> Result<usize, Error> {
+        let (labels, tokens) = labels(line);
+        for name in labels {
+            self.define(line, name, Symbol::Label(pc))?;
+            self.labels.push((name.to_string(), pc));
+        }
+        match tokens.first() {
+            None => Ok(0),
+            Some(&"const") => {
+                if tokens.len() != 4 || tokens[2] != "=" {
+                    return Err(Error::token(line, span(line, &tokens), "Expected const <name> = <value>"));
+                }
+                let value = self.constant(line, tokens[3])?;
+                self.define(line, tokens[1], Symbol::Const(value))?;
+                Ok(0)
+            }
+            Some(&"reg") => {
+                if tokens.len() < 2 {
+                    return Err(Error::token(line, span(line, &tokens), "Expected reg <name>..."));
+                }
+                for name in &tokens[1..] {
+                    let r = self.set.register_count();
+                    self.define(line, name, Symbol::Reg(r))?;
+                    self.set.add_register(name);
+                }
+                Ok(0)
+            }
+            Some(_) => Ok(1),
+        }
+    }
+
+    fn operand(&self, line: &str, token: &str, pc: usize, target: bool) -> Result<Operand, Error> {
+        if let Ok(n) = token.parse::<i64>() {
+            return Ok(Operand::Imm(n));
+        }
+        match self.symbols.get(token) {
+            Some(&Symbol::Const(n)) => Ok(Operand::Imm(n)),
+            Some(&Symbol::Reg(r)) => Ok(Operand::Reg(r)),
+            Some(&Symbol::Label(l)) if target => Ok(Operand::Imm(l as i64 - pc as i64)),
+            Some(&Symbol::Label(_)) => Err(Error::token(line, token, "Labels can only be jump offsets")),
+            None => Err(Error::token(line, token, "Unknown register, constant or label")),
+        }
+    }
+
+    fn lower(&self, line: &str, pc: usize) -> Result<Option<Instruction>, Error> {
+        let (_, tokens) = labels(line);
+        match tokens.first() {
+            None | Some(&"const") | Some(&"reg") => return Ok(None),
+            Some(_) => (),
+        }
+        let op = tokens[0].parse::<Opcode>().map_err(|e| e.within(line, tokens[0]))?;
+        if !self.set.supports(op) {
+            return Err(Error::token(line, tokens[0], "Not in the instruction set"));
+        }
+        if tokens.len() - 1 != op.arity() {
+            return Err(Error::token(line, span(line, &tokens), format!("{} takes {} operand(s)", op, op.arity())));
+        }
+        let x = self.operand(line, tokens[1], pc, false)?;
+        if op.writes() {
+            if let Operand::Imm(_) = x {
+                return Err(Error::token(line, tokens[1], "Target isn't a register"));
+            }
+        }
+        let y = match tokens.get(2) {
+            Some(t) => Some(self.operand(line, t, pc, op.is_jump())?),
+            None => None,
+        };
+        Ok(Some(Instruction{op, x, y}))
+    }
+}
+
+// The part of line from the first to the last of the tokens
+fn span<'a>(line: &'a str, tokens: &[&str]) -> &'a str {
+    let pos = |t: &str| t.as_ptr() as usize - line.as_ptr() as usize;
+    let last = tokens[tokens.len() - 1];
+    &line[pos(tokens[0])..pos(last) + last.len()]
+}
+
+// Assembles source for the given instruction set
+pub fn assemble(set: &InstructionSet, source: &str) -> Result<Program, Error> {
+    let mut asm = Assembler{set: set.clone(), symbols: HashMap::new(), labels: Vec::new()};
+    for (i, r) in set.register_names().iter().enumerate() {
+        asm.symbols.insert(r.clone(), Symbol::Reg(i));
+    }
+
+    let mut pc = 0;
+    for (n, line) in source.lines().enumerate() {
+        pc += asm.declare(line, pc).map_err(|e| e.at_line(n + 1))?;
+    }
+
+    let mut code = Vec::new();
+    let mut lines = Vec::new();
+    for (n, line) in source.lines().enumerate() {
+        if let Some(ins) = asm.lower(line, code.len()).map_err(|e| e.at_line(n + 1))? {
+            code.push(ins);
+            lines.push(n + 1);
+        }
+    }
+
+    Ok(Program{set: asm.set, code, labels: asm.labels, lines})
+}
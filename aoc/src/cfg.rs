@@ -0,0 +1,452 @@
+// The control flow graph of a duet program. Blocks run straight through from
+// their first instruction to their last, control only enters at the top and
+// only leaves at the bottom. Jumps with a constant offset are resolved to the
+// block they go to, jumps with an offset in a register can go anywhere.
+//
+// A loop is closed by back edges, which go to a block that every path from
+// the entry to their start passes through. The loop is that header along
+// with every block that reaches one of the back edges without passing the
+// header. Jumping into the middle of a loop from outside doesn't make one.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::iter;
+
+use asm::Program;
+use duet::Instruction;
+use duet::InstructionSet;
+use duet::Opcode;
+use duet::Operand;
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum Dest {
+    Block(usize),
+    // Off either end of the program
+    Exit,
+    // A jump by the value of a register
+    Unknown,
+}
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub struct Edge {
+    pub to: Dest,
+    // Taken by jumping rather than falling through
+    pub jump: bool,
+}
+
+// The instructions from start up to, but not including, end
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Block {
+    pub start: usize,
+    pub end: usize,
+    pub succ: Vec<Edge>,
+    pub pred: Vec<usize>,
+}
+
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Loop {
+    pub header: usize,
+    // The blocks jumping back to the header
+    pub latches: Vec<usize>,
+    // Every block of the loop in order, header included
+    pub blocks: Vec<usize>,
+}
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+enum Target {
+    Pc(i64),
+    Unknown,
+}
+
+// Whether the instruction at pc falls through to the next one and where it
+// may jump to
+fn flow(pc: usize, ins: &Instruction) -> (bool, Option<Target>) {
+    if !ins.op.is_jump() {
+        return (true, None);
+    }
+    let taken = match ins.x {
+        Operand::Imm(x) if ins.op == Opcode::Jgz => Some(x > 0),
+        Operand::Imm(x) => Some(x != 0),
+        Operand::Reg(_) => None,
+    };
+    let target = match ins.y {
+        Some(Operand::Imm(y)) => Target::Pc(pc as i64 + y),
+        _ => Target::Unknown,
+    };
+    match taken {
+        Some(true) => (false, Some(target)),
+        Some(false) => (true, None),
+        None => (true, Some(target)),
+    }
+}
+
+#[derive(Debug,Clone)]
+pub struct Cfg {
+    code: Vec<Instruction>,
+    blocks: Vec<Block>,
+    block_of: Vec<usize>,
+    labels: BTreeMap<usize, String>,
+    loops: Vec<Loop>,
+    reachable: Vec<bool>,
+    dominators: Vec<Vec<bool>>,
+}
+
+impl Cfg {
+    // Jump targets are labelled with their position, like L12. A jump to
+    // just after the last instruction goes to end.
+    pub fn new(code: &[Instruction]) -> Cfg {
+        Cfg::with_labels(code, &[])
+    }
+
+    // Keeps the labels of the source where there are any. Generated ones
+    // that the source already uses get a suffix, like L3_1.
+    pub fn from_program(p: &Program) -> Cfg {
+        Cfg::with_labels(&p.code, &p.labels)
+    }
+
+    fn with_labels(code: &[Instruction], names: &[(String, usize)]) -> Cfg {
+        let len = code.len();
+        let flows: Vec<_> = code.iter().enumerate().map(|(pc, ins)| flow(pc, ins)).collect();
+
+        let mut leader = vec![false; len + 1];
+        leader[0] = true;
+        let mut targets = BTreeSet::new();
+        for (pc, &(_, target)) in flows.iter().enumerate() {
+            if let Some(target) = target {
+                leader[pc + 1] = true;
+                if let Target::Pc(t) = target {
+                    if t >= 0 && t as usize <= len {
+                        leader[t as usize] = true;
+                        targets.insert(t as usize);
+                    }
+                }
+            }
+        }
+        let mut labels = BTreeMap::new();
+        for &(ref name, pc) in names.iter().rev() {
+            if targets.contains(&pc) {
+                labels.insert(pc, name.clone());
+            }
+        }
+        let taken: BTreeSet<_> = names.iter().map(|(name, _)| name.as_str()).collect();
+        for &t in &targets {
+            labels.entry(t).or_insert_with(|| {
+                let base = if t == len { String::from("end") } else { format!("L{}", t) };
+                iter::once(base.clone())
+                    .chain((1..).map(|k| format!("{}_{}", base, k)))
+                    .find(|n| !taken.contains(n.as_str()))
+                    .unwrap()
+            });
+        }
+
+        let mut blocks = Vec::new();
+        let mut block_of = vec![0; len];
+        for pc in 0..len {
+            if leader[pc] {
+                blocks.push(Block{start: pc, end: pc, succ: Vec::new(), pred: Vec::new()});
+            }
+            block_of[pc] = blocks.len() - 1;
+            blocks.last_mut().unwrap().end = pc + 1;
+        }
+
+        let dest = |t: i64| if t >= 0 && (t as usize) < len { Dest::Block(block_of[t as usize]) } else { Dest::Exit };
+        for b in &mut blocks {
+            let last = b.end - 1;
+            let (falls, target) = flows[last];
+            if let Some(target) = target {
+                let to = match target {
+                    Target::Pc(t) => dest(t),
+                    Target::Unknown => Dest::Unknown,
+                };
+                b.succ.push(Edge{to, jump: true});
+            }
+            if falls {
+                let edge = Edge{to: dest(b.end as i64), jump: false};
+                if !b.succ.contains(&edge) {
+                    b.succ.push(edge);
+                }
+            }
+        }
+        for i in 0..blocks.len() {
+            for e in blocks[i].succ.clone() {
+                if let Dest::Block(j) = e.to {
+                    if !blocks[j].pred.contains(&i) {
+                        blocks[j].pred.push(i);
+                    }
+                }
+            }
+        }
+
+        let mut cfg = Cfg{code: code.to_vec(), blocks, block_of, labels, loops: Vec::new(),
+                           reachable: Vec::new(), dominators: Vec::new()};
+        cfg.find_loops();
+        cfg
+    }
+
+    fn find_loops(&mut self) {
+        let n = self.blocks.len();
+        self.reachable = vec![false; n];
+        let mut todo = if n > 0 { vec![0] } else { Vec::new() };
+        while let Some(b) = todo.pop() {
+            if !self.reachable[b] {
+                self.reachable[b] = true;
+                todo.extend(self.successors(b));
+            }
+        }
+
+        // Every block dominates itself, the rest is narrowed down from all
+        // blocks until nothing changes
+        self.dominators = (0..n).map(|b| (0..n).map(|d| b != 0 || d == 0).collect()).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in 1..n {
+                let mut dom = vec![true; n];
+                for &p in self.blocks[b].pred.iter().filter(|&&p| self.reachable[p]) {
+                    for (d, &pd) in dom.iter_mut().zip(&self.dominators[p]) {
+                        *d &= pd;
+                    }
+                }
+                dom[b] = true;
+                if dom != self.dominators[b] {
+                    self.dominators[b] = dom;
+                    changed = true;
+                }
+            }
+        }
+
+        // An edge back to a block that dominates it closes a loop
+        let mut back: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for b in (0..n).filter(|&b| self.reachable[b]) {
+            for h in self.successors(b) {
+                if self.dominates(h, b) {
+                    back.entry(h).or_default().push(b);
+                }
+            }
+        }
+
+        for (header, latches) in back {
+            let mut inside = vec![false; n];
+            inside[header] = true;
+            let mut todo = latches.clone();
+            while let Some(b) = todo.pop() {
+                if !inside[b] {
+                    inside[b] = true;
+                    todo.extend(&self.blocks[b].pred);
+                }
+            }
+            let blocks = (0..n).filter(|&b| inside[b]).collect();
+            self.loops.push(Loop{header, latches, blocks});
+        }
+    }
+
+    // The blocks control can go to from b
+    fn successors(&self, b: usize) -> impl Iterator<Item = usize> + '_ {
+        self.blocks[b].succ.iter().filter_map(|e| match e.to {
+            Dest::Block(s) => Some(s),
+            _ => None,
+        })
+    }
+
+    pub fn code(&self) -> &[Instruction] {
+        &self.code
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    // The block holding the instruction at pc
+    pub fn block_at(&self, pc: usize) -> Option<usize> {
+        self.block_of.get(pc).cloned()
+    }
+
+    pub fn label(&self, pc: usize) -> Option<&str> {
+        self.labels.get(&pc).map(|l| l.as_str())
+    }
+
+    // The loops by their header, outer loops first
+    pub fn loops(&self) -> &[Loop] {
+        &self.loops
+    }
+
+    // The number of loops the block is in
+    pub fn depth(&self, b: usize) -> usize {
+        self.loops.iter().filter(|l| l.blocks.contains(&b)).count()
+    }
+
+    // Whether every path from the entry to b passes through d. Unreachable
+    // blocks are dominated by everything.
+    pub fn dominates(&self, d: usize, b: usize) -> bool {
+        self.dominators[b][d]
+    }
+
+    pub fn is_reachable(&self, b: usize) -> bool {
+        self.reachable[b]
+    }
+
+    pub fn is_back_edge(&self, from: usize, to: usize) -> bool {
+        self.loops.iter().any(|l| l.header == to && l.latches.contains(&from))
+    }
+
+    // The instruction at pc with a constant jump offset replaced by its label
+    pub fn format(&self, set: &InstructionSet, pc: usize) -> String {
+        let ins = &self.code[pc];
+        if let (true, Some(Operand::Imm(y))) = (ins.op.is_jump(), ins.y) {
+            let t = pc as i64 + y;
+            if let Some(l) = if t >= 0 { self.label(t as usize) } else { None } {
+                return format!("{} {} {}", ins.op, set.format_operand(ins.x), l);
+            }
+        }
+        set.format(ins)
+    }
+
+    // Where the jump at pc goes and when
+    fn describe_jump(&self, set: &InstructionSet, pc: usize) -> Option<String> {
+        let ins = &self.code[pc];
+        let (falls, target) = flow(pc, ins);
+        let to = match target? {
+            Target::Pc(t) if t >= 0 && (t as usize) < self.code.len() => t.to_string(),
+            Target::Pc(_) => String::from("exit"),
+            Target::Unknown => String::from("?"),
+        };
+        if !falls {
+            return Some(format!("-> {}", to));
+        }
+        let x = set.format_operand(ins.x);
+        let cond = if ins.op == Opcode::Jgz { "> 0" } else { "!= 0" };
+        Some(format!("-> {} if {} {}", to, x, cond))
+    }
+
+    fn dest_name(&self, e: &Edge) -> String {
+        match e.to {
+            Dest::Block(b) => format!("b{}", b),
+            Dest::Exit => String::from("exit"),
+            Dest::Unknown => String::from("?"),
+        }
+    }
+
+    // The program with its labels, one comment line per block and the
+    // position and jump target of every instruction, after the registers
+    // the program added. It assembles back to the same program.
+    pub fn listing(&self, set: &InstructionSet) -> String {
+        let width = self.code.iter().enumerate()
+            .map(|(pc, _)| self.format(set, pc).len())
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        let added = set.added_registers();
+        if !added.is_empty() {
+            writeln!(out, "reg {}", added.join(" ")).unwrap();
+        }
+        for (i, b) in self.blocks.iter().enumerate() {
+            let pred: Vec<_> = b.pred.iter().map(|p| format!("b{}", p)).collect();
+            let succ: Vec<_> = b.succ.iter().map(|e| self.dest_name(e)).collect();
+            write!(out, "# b{} [{}, {})", i, b.start, b.end).unwrap();
+            if i == 0 {
+                out.push_str(" entry");
+            }
+            if !pred.is_empty() {
+                write!(out, " <- {}", pred.join(" ")).unwrap();
+            }
+            if !succ.is_empty() {
+                write!(out, " -> {}", succ.join(" ")).unwrap();
+            }
+            if !self.is_reachable(i) {
+                out.push_str(", unreachable");
+            }
+            for (n, l) in self.loops.iter().enumerate() {
+                if l.header == i {
+                    write!(out, ", loop {} header", n).unwrap();
+                }
+            }
+            let depth = self.depth(i);
+            if depth > 0 {
+                write!(out, ", depth {}", depth).unwrap();
+            }
+            out.push('\n');
+
+            for pc in b.start..b.end {
+                let label = match self.label(pc) {
+                    Some(l) => format!("{}:", l),
+                    None => String::new(),
+                };
+                write!(out, "{:7} {:w$}  # {:3}", label, self.format(set, pc), pc, w = width).unwrap();
+                if let Some(j) = self.describe_jump(set, pc) {
+                    write!(out, " {}", j).unwrap();
+                }
+                out.push('\n');
+            }
+        }
+        if let Some(l) = self.label(self.code.len()) {
+            writeln!(out, "{}:", l).unwrap();
+        }
+        out
+    }
+
+    // The graph in Graphviz format. Loop headers are drawn with a double
+    // border and back edges dashed.
+    pub fn dot(&self, set: &InstructionSet) -> String {
+        let mut out = String::new();
+        out.push_str("digraph cfg {\n");
+        out.push_str("    node [shape=box, fontname=monospace];\n");
+        out.push_str("    entry [shape=point];\n");
+        let exits = self.blocks.iter().flat_map(|b| &b.succ);
+        if exits.clone().any(|e| e.to == Dest::Exit) || self.blocks.is_empty() {
+            out.push_str("    exit [shape=doublecircle, label=\"exit\"];\n");
+        }
+        if exits.clone().any(|e| e.to == Dest::Unknown) {
+            out.push_str("    unknown [shape=diamond, label=\"?\"];\n");
+        }
+
+        for (i, b) in self.blocks.iter().enumerate() {
+            let mut label = format!("b{}\\l", i);
+            for pc in b.start..b.end {
+                if let Some(l) = self.label(pc) {
+                    write!(label, "{}:\\l", l).unwrap();
+                }
+                write!(label, "{:3}  {}\\l", pc, self.format(set, pc)).unwrap();
+            }
+            let mut attrs = format!("label=\"{}\"", label);
+            if self.loops.iter().any(|l| l.header == i) {
+                attrs.push_str(", peripheries=2");
+            }
+            if !self.is_reachable(i) {
+                attrs.push_str(", style=dotted");
+            }
+            writeln!(out, "    b{} [{}];", i, attrs).unwrap();
+        }
+
+        match self.blocks.first() {
+            Some(_) => out.push_str("    entry -> b0;\n"),
+            None => out.push_str("    entry -> exit;\n"),
+        }
+        for (i, b) in self.blocks.iter().enumerate() {
+            let conditional = b.succ.len() > 1;
+            for e in &b.succ {
+                let to = match e.to {
+                    Dest::Block(t) => format!("b{}", t),
+                    Dest::Exit => String::from("exit"),
+                    Dest::Unknown => String::from("unknown"),
+                };
+                let mut attrs = Vec::new();
+                if conditional {
+                    attrs.push(if e.jump { "label=\"taken\"" } else { "label=\"else\"" });
+                }
+                if let Dest::Block(t) = e.to {
+                    if self.is_back_edge(i, t) {
+                        attrs.push("style=dashed");
+                    }
+                }
+                if attrs.is_empty() {
+                    writeln!(out, "    b{} -> {};", i, to).unwrap();
+                } else {
+                    writeln!(out, "    b{} -> {} [{}];", i, to, attrs.join(", ")).unwrap();
+                }
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
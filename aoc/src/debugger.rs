@@ -0,0 +1,473 @@
+// A debugger for duet programs. It runs one or more machines on the same
+// program the way duet::run_pair does: the current machine runs until it
+// blocks or halts, everything it sends goes to the next one, then it's the
+// next machine's turn. It's done once none of them can go on.
+//
+// It is driven by the text commands of the help below, one per line. An
+// empty line repeats the last command. Instructions can be referred to by
+// their labels in the source or those of the control flow graph listing.
+
+use std::fmt::Write;
+
+use asm::Program;
+use cfg::Cfg;
+use duet::Io;
+use duet::Operand;
+use duet::State;
+use duet::Vm;
+use Error;
+
+pub const HELP: &str = "\
+step [n]                  run n instructions, 1 by default
+continue                  run until a breakpoint or watchpoint is hit, or no machine can go on
+break <pc|label> [if <c>] stop before the instruction, if the condition holds
+break if <c>              stop when the condition turns true, like \"b > 10\" or \"g == 0\"
+watch <reg>               stop when the register changes
+delete <n>                remove breakpoint or watchpoint n
+info                      list breakpoints and watchpoints
+regs                      show the registers of every machine
+print <reg>               show one register of every machine
+io                        show what every machine holds, like its inbox
+list [all]                show the instructions around pc, or all of them, with how often they ran
+counts                    show how often every opcode ran
+help                      show this
+";
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum Cmp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Cmp {
+    fn parse(s: &str, token: &str) -> Result<Cmp, Error> {
+        match token {
+            "==" => Ok(Cmp::Eq),
+            "!=" => Ok(Cmp::Ne),
+            "<" => Ok(Cmp::Lt),
+            "<=" => Ok(Cmp::Le),
+            ">" => Ok(Cmp::Gt),
+            ">=" => Ok(Cmp::Ge),
+            _ => Err(Error::token(s, token, "Expected one of == != < <= > >=")),
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Cmp::Eq => "==",
+            Cmp::Ne => "!=",
+            Cmp::Lt => "<",
+            Cmp::Le => "<=",
+            Cmp::Gt => ">",
+            Cmp::Ge => ">=",
+        }
+    }
+
+    fn apply(self, a: i64, b: i64) -> bool {
+        match self {
+            Cmp::Eq => a == b,
+            Cmp::Ne => a != b,
+            Cmp::Lt => a < b,
+            Cmp::Le => a <= b,
+            Cmp::Gt => a > b,
+            Cmp::Ge => a >= b,
+        }
+    }
+}
+
+// A register compared to a number or another register
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub struct Condition {
+    pub reg: usize,
+    pub cmp: Cmp,
+    pub value: Operand,
+}
+
+impl Condition {
+    pub fn holds(&self, regs: &[i64]) -> bool {
+        let value = match self.value {
+            Operand::Imm(n) => n,
+            Operand::Reg(r) => regs[r],
+        };
+        self.cmp.apply(regs[self.reg], value)
+    }
+}
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum Point {
+    // Before executing pc, if the condition holds. Without a pc, when the
+    // condition turns true.
+    Break(Option<usize>, Option<Condition>),
+    // When the register changes
+    Watch(usize),
+}
+
+// Why running stopped
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum Event {
+    // After the requested number of steps
+    Step,
+    Break{id: usize, vm: usize},
+    Watch{id: usize, vm: usize, reg: usize, old: i64, new: i64},
+    // A rcv stopped the machine
+    Stopped(usize),
+    // No machine can go on
+    Done,
+}
+
+pub struct Debugger<T> {
+    program: Program,
+    cfg: Cfg,
+    vms: Vec<Vm<T>>,
+    current: usize,
+    points: Vec<(usize, Point)>,
+    next_id: usize,
+    last: String,
+    // Whether the breakpoints at the pc of every machine have been checked
+    // since it got there
+    checked: Vec<bool>,
+}
+
+impl<T: Io> Debugger<T> {
+    pub fn new(program: Program, vms: Vec<Vm<T>>) -> Debugger<T> {
+        assert!(!vms.is_empty(), "The debugger needs a machine to run");
+        let cfg = Cfg::from_program(&program);
+        let checked = vec![false; vms.len()];
+        Debugger{program, cfg, vms, current: 0, points: Vec::new(), next_id: 1, last: String::new(), checked}
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    pub fn vms(&self) -> &[Vm<T>] {
+        &self.vms
+    }
+
+    // The machine that runs next
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    // Adds a breakpoint or watchpoint and returns its number
+    pub fn add(&mut self, point: Point) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.points.push((id, point));
+        id
+    }
+
+    pub fn delete(&mut self, id: usize) -> bool {
+        let len = self.points.len();
+        self.points.retain(|&(i, _)| i != id);
+        self.points.len() < len
+    }
+
+    pub fn points(&self) -> &[(usize, Point)] {
+        &self.points
+    }
+
+    // Runs a single instruction on the first machine that can go on, starting
+    // with the current one. A breakpoint at a pc nothing was reported for yet,
+    // like the first one, stops before running anything.
+    pub fn step(&mut self) -> Result<Event, Error> {
+        let n = self.vms.len();
+        for _ in 0..n {
+            let i = self.current;
+            if !self.checked[i] {
+                self.checked[i] = true;
+                if let Some(event) = self.at_break(i) {
+                    return Ok(event);
+                }
+            }
+            let before = self.vms[i].registers().to_vec();
+            let steps = self.vms[i].steps();
+            let state = self.vms[i].step()?;
+            for v in self.vms[i].io_mut().take_outbox() {
+                self.vms[(i + 1) % n].io_mut().deliver(v);
+            }
+            if self.vms[i].steps() == steps {
+                self.current = (i + 1) % n;
+                continue;
+            }
+            if let Some(event) = self.check(i, &before) {
+                self.checked[i] = false;
+                return Ok(event);
+            }
+            if let Some(event) = self.at_break(i) {
+                return Ok(event);
+            }
+            if state == State::Stopped {
+                return Ok(Event::Stopped(i));
+            }
+            return Ok(Event::Step);
+        }
+        Ok(Event::Done)
+    }
+
+    // Runs until something other than a plain step happens
+    pub fn run(&mut self) -> Result<Event, Error> {
+        loop {
+            let event = self.step()?;
+            if event != Event::Step {
+                return Ok(event);
+            }
+        }
+    }
+
+    // The watchpoints and conditions turning true from the last step of
+    // machine i
+    fn check(&self, i: usize, before: &[i64]) -> Option<Event> {
+        let regs = self.vms[i].registers();
+        for &(id, point) in &self.points {
+            let hit = match point {
+                Point::Watch(reg) if regs[reg] != before[reg] => {
+                    return Some(Event::Watch{id, vm: i, reg, old: before[reg], new: regs[reg]});
+                }
+                Point::Break(None, Some(c)) => c.holds(regs) && !c.holds(before),
+                _ => false,
+            };
+            if hit {
+                return Some(Event::Break{id, vm: i});
+            }
+        }
+        None
+    }
+
+    // The first breakpoint at the pc of machine i whose condition holds
+    fn at_break(&self, i: usize) -> Option<Event> {
+        let vm = &self.vms[i];
+        self.points.iter()
+            .find(|&&(_, point)| match point {
+                Point::Break(Some(pc), cond) => {
+                    vm.pc() == pc as i64 && cond.is_none_or(|c| c.holds(vm.registers()))
+                }
+                _ => false,
+            })
+            .map(|&(id, _)| Event::Break{id, vm: i})
+    }
+
+    fn register(&self, s: &str, token: &str) -> Result<usize, Error> {
+        self.program.set.register(token).ok_or_else(|| Error::token(s, token, "Unknown register"))
+    }
+
+    // A pc or a label
+    fn location(&self, s: &str, token: &str) -> Result<usize, Error> {
+        let pc = match token.parse::<usize>() {
+            Ok(pc) => pc,
+            Err(_) => self.program.labels.iter()
+                .find(|l| l.0 == token)
+                .map(|l| l.1)
+                .or_else(|| (0..self.program.code.len()).find(|&pc| self.cfg.label(pc) == Some(token)))
+                .ok_or_else(|| Error::token(s, token, "Unknown label"))?,
+        };
+        if pc >= self.program.code.len() {
+            return Err(Error::token(s, token, "Not in the program"));
+        }
+        Ok(pc)
+    }
+
+    fn condition(&self, s: &str, tokens: &[&str]) -> Result<Condition, Error> {
+        if tokens.len() != 3 {
+            return Err(Error::from(format!("Expected <reg> <op> <value> in \"{}\"", s.trim())));
+        }
+        let reg = self.register(s, tokens[0])?;
+        let cmp = Cmp::parse(s, tokens[1])?;
+        let value = match tokens[2].parse::<i64>() {
+            Ok(n) => Operand::Imm(n),
+            Err(_) => Operand::Reg(self.register(s, tokens[2])?),
+        };
+        Ok(Condition{reg, cmp, value})
+    }
+
+    // The position of machine i, prefixed by the machine if there are
+    // several
+    fn position(&self, i: usize) -> String {
+        let vm = &self.vms[i];
+        let prefix = if self.vms.len() > 1 { format!("vm {} ", i) } else { String::new() };
+        match vm.current() {
+            Some(_) => {
+                let pc = vm.pc() as usize;
+                format!("{}at {}: {}", prefix, self.pc_label(pc), self.cfg.format(&self.program.set, pc))
+            }
+            None => format!("{}halted at {}", prefix, vm.pc()),
+        }
+    }
+
+    fn label(&self, pc: usize) -> Option<&str> {
+        self.program.labels_at(pc).next().or_else(|| self.cfg.label(pc))
+    }
+
+    fn pc_label(&self, pc: usize) -> String {
+        match self.label(pc) {
+            Some(l) => format!("{} ({})", pc, l),
+            None => pc.to_string(),
+        }
+    }
+
+    fn describe(&self, id: usize, point: &Point) -> String {
+        let set = &self.program.set;
+        let cond = |c: &Condition| {
+            format!("{} {} {}", set.register_name(c.reg), c.cmp.symbol(), set.format_operand(c.value))
+        };
+        match *point {
+            Point::Break(Some(pc), None) => format!("{}: break at {}", id, self.pc_label(pc)),
+            Point::Break(Some(pc), Some(c)) => format!("{}: break at {} if {}", id, self.pc_label(pc), cond(&c)),
+            Point::Break(None, Some(c)) => format!("{}: break if {}", id, cond(&c)),
+            Point::Break(None, None) => format!("{}: break", id),
+            Point::Watch(r) => format!("{}: watch {}", id, set.register_name(r)),
+        }
+    }
+
+    fn report(&self, event: Event) -> String {
+        let set = &self.program.set;
+        let current = self.current;
+        match event {
+            Event::Step => self.position(current),
+            Event::Break{id, vm} => format!("Breakpoint {}, {}", id, self.position(vm)),
+            Event::Watch{id, vm, reg, old, new} => format!("Watchpoint {}, {} changed from {} to {}\n{}",
+                id, set.register_name(reg), old, new, self.position(vm)),
+            Event::Stopped(vm) => format!("Stopped by rcv, {}\n{}", self.vms[vm].io().status(), self.position(vm)),
+            Event::Done => {
+                let states: Vec<_> = self.vms.iter().map(|vm| format!("{:?}", vm.state())).collect();
+                format!("No machine can go on: {}", states.join(", "))
+            }
+        }
+    }
+
+    fn list(&self, all: bool) -> String {
+        let vm = &self.vms[self.current];
+        let code = &self.program.code;
+        let (from, to) = if all {
+            (0, code.len())
+        } else {
+            let pc = vm.pc().max(0).min(code.len() as i64) as usize;
+            (pc.saturating_sub(5), (pc + 6).min(code.len()))
+        };
+        let mut out = String::new();
+        for pc in from..to {
+            let mark = if vm.pc() == pc as i64 { "=>" } else { "  " };
+            let brk = self.points.iter().any(|&(_, p)| match p {
+                Point::Break(Some(b), _) => b == pc,
+                _ => false,
+            });
+            let hits: Vec<_> = self.vms.iter().map(|vm| format!("{:8}", vm.hits()[pc])).collect();
+            let label = match self.label(pc) {
+                Some(l) => format!("{}:", l),
+                None => String::new(),
+            };
+            writeln!(out, "{}{}{:4}  {:8}{:20}{}", mark, if brk { '*' } else { ' ' }, pc, label,
+                     self.cfg.format(&self.program.set, pc), hits.join("")).unwrap();
+        }
+        out.trim_end().to_string()
+    }
+
+    fn registers(&self) -> String {
+        let set = &self.program.set;
+        let mut out = String::new();
+        for (i, vm) in self.vms.iter().enumerate() {
+            let regs: Vec<_> = vm.registers().iter().enumerate()
+                .map(|(r, v)| format!("{}={}", set.register_name(r), v))
+                .collect();
+            if self.vms.len() > 1 {
+                write!(out, "vm {}: ", i).unwrap();
+            }
+            writeln!(out, "{}", regs.join(" ")).unwrap();
+        }
+        out.trim_end().to_string()
+    }
+
+    fn each_vm<F: Fn(&Vm<T>) -> String>(&self, f: F) -> String {
+        let lines: Vec<_> = self.vms.iter().enumerate().map(|(i, vm)| {
+            if self.vms.len() > 1 { format!("vm {}: {}", i, f(vm)) } else { f(vm) }
+        }).collect();
+        lines.join("\n")
+    }
+
+    // Runs a command and returns what it has to say
+    pub fn command(&mut self, line: &str) -> Result<String, Error> {
+        let line = if line.trim().is_empty() { self.last.clone() } else { line.to_string() };
+        let s = line.as_str();
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        if tokens.is_empty() {
+            return Ok(String::new());
+        }
+        self.last = line.clone();
+
+        match tokens[0] {
+            "s" | "step" => {
+                let n = match tokens.get(1) {
+                    Some(t) => ::parse_token::<usize>(s, t)?,
+                    None => 1,
+                };
+                let mut event = Event::Step;
+                for _ in 0..n {
+                    event = self.step()?;
+                    if event != Event::Step {
+                        break;
+                    }
+                }
+                Ok(self.report(event))
+            }
+            "c" | "continue" => {
+                let event = self.run()?;
+                Ok(self.report(event))
+            }
+            "b" | "break" => {
+                let point = match tokens.get(1) {
+                    Some(&"if") => Point::Break(None, Some(self.condition(s, &tokens[2..])?)),
+                    Some(t) => {
+                        let pc = self.location(s, t)?;
+                        match tokens.get(2) {
+                            Some(&"if") => Point::Break(Some(pc), Some(self.condition(s, &tokens[3..])?)),
+                            Some(t) => return Err(Error::token(s, t, "Expected if")),
+                            None => Point::Break(Some(pc), None),
+                        }
+                    }
+                    None => return Err(Error::from("Expected break <pc|label> [if <cond>] or break if <cond>")),
+                };
+                let id = self.add(point);
+                Ok(format!("Breakpoint {}", self.describe(id, &point)))
+            }
+            "w" | "watch" => {
+                let t = tokens.get(1).ok_or_else(|| Error::from("Expected watch <reg>"))?;
+                let point = Point::Watch(self.register(s, t)?);
+                let id = self.add(point);
+                Ok(format!("Watchpoint {}", self.describe(id, &point)))
+            }
+            "d" | "delete" => {
+                let t = tokens.get(1).ok_or_else(|| Error::from("Expected delete <n>"))?;
+                let id = ::parse_token::<usize>(s, t)?;
+                if !self.delete(id) {
+                    return Err(Error::token(s, t, "No such breakpoint or watchpoint"));
+                }
+                Ok(format!("Deleted {}", id))
+            }
+            "i" | "info" => {
+                if self.points.is_empty() {
+                    return Ok(String::from("No breakpoints or watchpoints"));
+                }
+                let points: Vec<_> = self.points.iter().map(|&(id, p)| self.describe(id, &p)).collect();
+                Ok(points.join("\n"))
+            }
+            "r" | "regs" => Ok(self.registers()),
+            "p" | "print" => {
+                let t = tokens.get(1).ok_or_else(|| Error::from("Expected print <reg>"))?;
+                let r = self.register(s, t)?;
+                Ok(self.each_vm(|vm| format!("{} = {}", t, vm.reg(r))))
+            }
+            "io" => Ok(self.each_vm(|vm| vm.io().status())),
+            "l" | "list" => Ok(self.list(tokens.get(1) == Some(&"all"))),
+            "counts" => Ok(self.each_vm(|vm| {
+                let counts: Vec<_> = vm.counts().iter().map(|&(op, n)| format!("{} {}", op, n)).collect();
+                format!("{} steps: {}", vm.steps(), counts.join(", "))
+            })),
+            "h" | "help" => Ok(HELP.trim_end().to_string()),
+            t => Err(Error::token(s, t, "Unknown command, try help")),
+        }
+    }
+}
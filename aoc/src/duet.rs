@@ -0,0 +1,639 @@
+// The assembly of days 18 and 23. An instruction set picks which opcodes a
+// program may use and names the registers, the machine runs any program
+// built from it. What snd and rcv do is up to the Io the machine is given:
+// playing and recovering sounds, or passing messages between two machines.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use Error;
+use opt;
+use opt::Fused;
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub enum Opcode {
+    Set,
+    Add,
+    Sub,
+    Mul,
+    Mod,
+    Snd,
+    Rcv,
+    Jgz,
+    Jnz,
+}
+
+impl Opcode {
+    pub fn all() -> [Opcode; 9] {
+        [Opcode::Set, Opcode::Add, Opcode::Sub, Opcode::Mul, Opcode::Mod,
+         Opcode::Snd, Opcode::Rcv, Opcode::Jgz, Opcode::Jnz]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Opcode::Set => "set",
+            Opcode::Add => "add",
+            Opcode::Sub => "sub",
+            Opcode::Mul => "mul",
+            Opcode::Mod => "mod",
+            Opcode::Snd => "snd",
+            Opcode::Rcv => "rcv",
+            Opcode::Jgz => "jgz",
+            Opcode::Jnz => "jnz",
+        }
+    }
+
+    // The number of operands
+    pub fn arity(self) -> usize {
+        match self {
+            Opcode::Snd | Opcode::Rcv => 1,
+            _ => 2,
+        }
+    }
+
+    // Whether the first operand is written to and has to be a register
+    pub fn writes(self) -> bool {
+        matches!(self, Opcode::Set | Opcode::Add | Opcode::Sub | Opcode::Mul | Opcode::Mod)
+    }
+
+    pub fn is_jump(self) -> bool {
+        self == Opcode::Jgz || self == Opcode::Jnz
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for Opcode {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Opcode, Error> {
+        Opcode::all().iter()
+            .find(|op| op.name() == s.trim())
+            .cloned()
+            .ok_or_else(|| Error::token(s, s.trim(), "Invalid instruction"))
+    }
+}
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum Operand {
+    Imm(i64),
+    Reg(usize),
+}
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub struct Instruction {
+    pub op: Opcode,
+    pub x: Operand,
+    pub y: Option<Operand>,
+}
+
+// The opcodes a program may use and the names of its registers
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct InstructionSet {
+    opcodes: Vec<Opcode>,
+    registers: Vec<String>,
+    // How many of the registers the set came with, the rest were added
+    base: usize,
+}
+
+impl InstructionSet {
+    // Only the given opcodes and no registers yet
+    pub fn new<I: IntoIterator<Item = Opcode>>(opcodes: I) -> InstructionSet {
+        InstructionSet{opcodes: opcodes.into_iter().collect(), registers: Vec::new(), base: 0}
+    }
+
+    // The sound card and the message passing programs of day 18
+    pub fn duet() -> InstructionSet {
+        use self::Opcode::*;
+        InstructionSet::new(vec![Set, Add, Mul, Mod, Snd, Rcv, Jgz]).registers('a', 'z')
+    }
+
+    // The coprocessor of day 23
+    pub fn coprocessor() -> InstructionSet {
+        use self::Opcode::*;
+        InstructionSet::new(vec![Set, Sub, Mul, Jnz]).registers('a', 'h')
+    }
+
+    // Every opcode and registers a to z
+    pub fn full() -> InstructionSet {
+        InstructionSet::new(Opcode::all().to_vec()).registers('a', 'z')
+    }
+
+    // Single letter registers from first to last
+    pub fn registers(mut self, first: char, last: char) -> InstructionSet {
+        self.registers = (first..=last).map(|c| c.to_string()).collect();
+        self.base = self.registers.len();
+        self
+    }
+
+    // Registers with the given names, in order
+    pub fn named<I, S>(mut self, names: I) -> InstructionSet
+        where I: IntoIterator<Item = S>,
+              S: Into<String>,
+    {
+        self.registers = names.into_iter().map(Into::into).collect();
+        self.base = self.registers.len();
+        self
+    }
+
+    // Adds a register after the others and returns it
+    pub fn add_register(&mut self, name: &str) -> usize {
+        self.registers.push(name.to_string());
+        self.registers.len() - 1
+    }
+
+    // The registers added after the ones the set came with
+    pub fn added_registers(&self) -> &[String] {
+        &self.registers[self.base..]
+    }
+
+    pub fn opcodes(&self) -> &[Opcode] {
+        &self.opcodes
+    }
+
+    pub fn supports(&self, op: Opcode) -> bool {
+        self.opcodes.contains(&op)
+    }
+
+    pub fn register_names(&self) -> &[String] {
+        &self.registers
+    }
+
+    pub fn register_count(&self) -> usize {
+        self.registers.len()
+    }
+
+    pub fn register(&self, name: &str) -> Option<usize> {
+        self.registers.iter().position(|r| r == name)
+    }
+
+    pub fn register_name(&self, r: usize) -> &str {
+        &self.registers[r]
+    }
+
+    // A number or the name of a register
+    pub fn parse_operand(&self, s: &str) -> Result<Operand, Error> {
+        if let Ok(n) = s.parse::<i64>() {
+            return Ok(Operand::Imm(n));
+        }
+        self.register(s)
+            .map(Operand::Reg)
+            .ok_or_else(|| Error::token(s, s, "Invalid register name"))
+    }
+
+    pub fn parse_instruction(&self, s: &str) -> Result<Instruction, Error> {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        if tokens.len() < 2 || tokens.len() > 3 {
+            return Err(Error::token(s, s.trim(), "Expected <instruction> <operand> [<operand>]"));
+        }
+        let op = tokens[0].parse::<Opcode>().map_err(|e| e.within(s, tokens[0]))?;
+        if !self.supports(op) {
+            return Err(Error::token(s, tokens[0], "Not in the instruction set"));
+        }
+        if tokens.len() - 1 != op.arity() {
+            return Err(Error::token(s, s.trim(), format!("{} takes {} operand(s)", op, op.arity())));
+        }
+        let parse = |t: &str| self.parse_operand(t).map_err(|e| e.within(s, t));
+        let x = parse(tokens[1])?;
+        if op.writes() {
+            if let Operand::Imm(_) = x {
+                return Err(Error::token(s, tokens[1], "Target isn't a register"));
+            }
+        }
+        let y = match tokens.get(2) {
+            Some(t) => Some(parse(t)?),
+            None => None,
+        };
+        Ok(Instruction{op, x, y})
+    }
+
+    // A program of one instruction per line
+    pub fn parse(&self, input: &str) -> Result<Vec<Instruction>, Error> {
+        ::map_lines(input, |l| self.parse_instruction(l))
+    }
+
+    pub fn format_operand(&self, o: Operand) -> String {
+        match o {
+            Operand::Imm(n) => n.to_string(),
+            Operand::Reg(r) => self.registers[r].clone(),
+        }
+    }
+
+    // An instruction the way parse_instruction reads it
+    pub fn format(&self, ins: &Instruction) -> String {
+        match ins.y {
+            Some(y) => format!("{} {} {}", ins.op, self.format_operand(ins.x), self.format_operand(y)),
+            None => format!("{} {}", ins.op, self.format_operand(ins.x)),
+        }
+    }
+}
+
+// What a rcv does next
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum Receive {
+    // Nothing, go on with the next instruction
+    Skip,
+    // Write the value to the register and go on
+    Store(i64),
+    // Wait on the rcv until there's something to receive
+    Block,
+    // Stop the machine after the rcv
+    Stop,
+}
+
+// The semantics of snd and rcv
+pub trait Io {
+    fn snd(&mut self, value: i64);
+    // Called with the current value of the operand of the rcv
+    fn rcv(&mut self, value: i64) -> Receive;
+
+    // Takes the messages sent for another machine, if the io passes any
+    fn take_outbox(&mut self) -> Vec<i64> {
+        Vec::new()
+    }
+
+    // A message from another machine
+    fn deliver(&mut self, _value: i64) {}
+
+    // What the io holds, for inspection
+    fn status(&self) -> String {
+        String::new()
+    }
+}
+
+// No sound and no messages, a rcv waits forever
+impl Io for () {
+    fn snd(&mut self, _: i64) {}
+    fn rcv(&mut self, _: i64) -> Receive {
+        Receive::Block
+    }
+}
+
+// Part 1 of day 18: snd plays a sound and rcv recovers the last one played,
+// unless its operand is zero. The first recovery stops the machine.
+#[derive(Debug,Clone,Default,PartialEq,Eq)]
+pub struct Sound {
+    played: Option<i64>,
+    recovered: Option<i64>,
+}
+
+impl Sound {
+    pub fn new() -> Sound {
+        Sound::default()
+    }
+
+    pub fn played(&self) -> Option<i64> {
+        self.played
+    }
+
+    pub fn recovered(&self) -> Option<i64> {
+        self.recovered
+    }
+}
+
+impl Io for Sound {
+    fn snd(&mut self, value: i64) {
+        self.played = Some(value);
+    }
+
+    fn rcv(&mut self, value: i64) -> Receive {
+        if value == 0 {
+            return Receive::Skip;
+        }
+        self.recovered = self.played;
+        debug!("Recovered {:?}", self.recovered);
+        Receive::Stop
+    }
+
+    fn status(&self) -> String {
+        let show = |v: Option<i64>| v.map_or(String::from("-"), |v| v.to_string());
+        format!("played {}, recovered {}", show(self.played), show(self.recovered))
+    }
+}
+
+// Part 2 of day 18: snd queues a message for the other program and rcv takes
+// the oldest received message, waiting for one if there is none.
+#[derive(Debug,Clone,Default,PartialEq,Eq)]
+pub struct Mailbox {
+    inbox: VecDeque<i64>,
+    outbox: VecDeque<i64>,
+    sent: usize,
+}
+
+impl Mailbox {
+    pub fn new() -> Mailbox {
+        Mailbox::default()
+    }
+
+    // Received and not yet taken by a rcv
+    pub fn inbox(&self) -> &VecDeque<i64> {
+        &self.inbox
+    }
+
+    // Sent and not yet delivered
+    pub fn outbox(&self) -> &VecDeque<i64> {
+        &self.outbox
+    }
+
+    // The number of messages sent so far
+    pub fn sent(&self) -> usize {
+        self.sent
+    }
+}
+
+impl Io for Mailbox {
+    fn snd(&mut self, value: i64) {
+        self.outbox.push_back(value);
+        self.sent += 1;
+    }
+
+    fn rcv(&mut self, _: i64) -> Receive {
+        match self.inbox.pop_front() {
+            Some(v) => Receive::Store(v),
+            None => Receive::Block,
+        }
+    }
+
+    fn take_outbox(&mut self) -> Vec<i64> {
+        self.outbox.drain(..).collect()
+    }
+
+    fn deliver(&mut self, value: i64) {
+        self.inbox.push_back(value);
+    }
+
+    fn status(&self) -> String {
+        let inbox: Vec<_> = self.inbox.iter().map(|v| v.to_string()).collect();
+        format!("inbox [{}], sent {}", inbox.join(", "), self.sent)
+    }
+}
+
+#[derive(Debug,Copy,Clone,PartialEq,Eq)]
+pub enum State {
+    Running,
+    // Waiting on a rcv
+    Blocked,
+    // Stopped by a rcv
+    Stopped,
+    // Jumped or ran off the program
+    Halted,
+}
+
+#[derive(Debug,Clone)]
+pub struct Vm<T> {
+    prog: Vec<Instruction>,
+    regs: Vec<i64>,
+    pc: i64,
+    io: T,
+    counts: Vec<usize>,
+    hits: Vec<usize>,
+    steps: usize,
+    state: State,
+    // The fused loop starting at every pc, see opt
+    fused: Vec<Option<Fused>>,
+}
+
+impl<T: Io> Vm<T> {
+    // All registers start out at zero
+    pub fn new(set: &InstructionSet, prog: &[Instruction], io: T) -> Vm<T> {
+        Vm {
+            prog: prog.to_vec(),
+            regs: vec![0; set.register_count()],
+            pc: 0,
+            io,
+            counts: vec![0; Opcode::all().len()],
+            hits: vec![0; prog.len()],
+            steps: 0,
+            state: State::Running,
+            fused: Vec::new(),
+        }
+    }
+
+    pub fn program(&self) -> &[Instruction] {
+        &self.prog
+    }
+
+    pub fn pc(&self) -> i64 {
+        self.pc
+    }
+
+    pub fn set_pc(&mut self, pc: i64) {
+        self.pc = pc;
+    }
+
+    // The instruction at pc, None once the machine ran off the program
+    pub fn current(&self) -> Option<Instruction> {
+        if self.pc < 0 {
+            return None;
+        }
+        self.prog.get(self.pc as usize).cloned()
+    }
+
+    pub fn registers(&self) -> &[i64] {
+        &self.regs
+    }
+
+    pub fn reg(&self, r: usize) -> i64 {
+        self.regs[r]
+    }
+
+    pub fn set_reg(&mut self, r: usize, value: i64) {
+        self.regs[r] = value;
+    }
+
+    pub fn io(&self) -> &T {
+        &self.io
+    }
+
+    pub fn io_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    // How often op was executed, a blocked rcv doesn't count
+    pub fn count(&self, op: Opcode) -> usize {
+        self.counts[op as usize]
+    }
+
+    // The counts of all executed opcodes
+    pub fn counts(&self) -> Vec<(Opcode, usize)> {
+        Opcode::all().iter()
+            .map(|&op| (op, self.count(op)))
+            .filter(|&(_, n)| n > 0)
+            .collect()
+    }
+
+    // How often every instruction of the program was executed
+    pub fn hits(&self) -> &[usize] {
+        &self.hits
+    }
+
+    // The number of executed instructions
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    // Fuses the loops of the program the optimizer knows, returns how many
+    pub fn optimize(&mut self) -> usize {
+        self.fused = vec![None; self.prog.len()];
+        let found = opt::find(&self.prog);
+        let n = found.len();
+        for f in found {
+            let start = f.start;
+            self.fused[start] = Some(f);
+        }
+        n
+    }
+
+    // Runs the loop fused at pc if there is one and it can, counting all of
+    // its instructions as executed
+    fn run_fused(&mut self) -> bool {
+        let fused = match self.fused.get(self.pc as usize) {
+            Some(Some(f)) => f,
+            _ => return false,
+        };
+        let hits = match fused.run(&mut self.regs) {
+            Some(hits) => hits,
+            None => return false,
+        };
+        trace!("{:3} fused {:?}", self.pc, fused.kind);
+        for (pc, n) in (fused.start..fused.end).zip(hits) {
+            self.hits[pc] += n;
+            self.counts[self.prog[pc].op as usize] += n;
+            self.steps += n;
+        }
+        self.pc = fused.end as i64;
+        true
+    }
+
+    fn value(&self, o: Operand) -> i64 {
+        match o {
+            Operand::Imm(n) => n,
+            Operand::Reg(r) => self.regs[r],
+        }
+    }
+
+    fn second(&self, ins: &Instruction) -> Result<i64, Error> {
+        match ins.y {
+            Some(y) => Ok(self.value(y)),
+            None => Err(Error::Solve(format!("Missing operand at pc {}", self.pc))),
+        }
+    }
+
+    fn write(&mut self, o: Operand, value: i64) -> Result<(), Error> {
+        match o {
+            Operand::Reg(r) => {
+                self.regs[r] = value;
+                Ok(())
+            }
+            Operand::Imm(_) => Err(Error::Solve(format!("Target isn't a register at pc {}", self.pc))),
+        }
+    }
+
+    // Executes a single instruction. A machine that is blocked tries its rcv
+    // again, one that stopped goes on after it.
+    pub fn step(&mut self) -> Result<State, Error> {
+        let ins = match self.current() {
+            Some(ins) => ins,
+            None => {
+                self.state = State::Halted;
+                return Ok(self.state);
+            }
+        };
+        if self.run_fused() {
+            self.state = State::Running;
+            return Ok(self.state);
+        }
+        let x = self.value(ins.x);
+        trace!("{:3} {:?} x: {}", self.pc, ins, x);
+
+        let mut next = self.pc + 1;
+        self.state = State::Running;
+        match ins.op {
+            Opcode::Set => {
+                let y = self.second(&ins)?;
+                self.write(ins.x, y)?;
+            }
+            Opcode::Add => {
+                let y = self.second(&ins)?;
+                self.write(ins.x, x.wrapping_add(y))?;
+            }
+            Opcode::Sub => {
+                let y = self.second(&ins)?;
+                self.write(ins.x, x.wrapping_sub(y))?;
+            }
+            Opcode::Mul => {
+                let y = self.second(&ins)?;
+                self.write(ins.x, x.wrapping_mul(y))?;
+            }
+            Opcode::Mod => {
+                let y = self.second(&ins)?;
+                if y == 0 {
+                    return Err(Error::Solve(format!("Division by zero at pc {}", self.pc)));
+                }
+                self.write(ins.x, x.wrapping_rem(y))?;
+            }
+            Opcode::Snd => self.io.snd(x),
+            Opcode::Rcv => match self.io.rcv(x) {
+                Receive::Skip => (),
+                Receive::Store(v) => self.write(ins.x, v)?,
+                Receive::Block => {
+                    self.state = State::Blocked;
+                    return Ok(self.state);
+                }
+                Receive::Stop => self.state = State::Stopped,
+            },
+            Opcode::Jgz => {
+                if x > 0 {
+                    next = self.pc.wrapping_add(self.second(&ins)?);
+                }
+            }
+            Opcode::Jnz => {
+                if x != 0 {
+                    next = self.pc.wrapping_add(self.second(&ins)?);
+                }
+            }
+        }
+        self.hits[self.pc as usize] += 1;
+        self.pc = next;
+        self.counts[ins.op as usize] += 1;
+        self.steps += 1;
+        Ok(self.state)
+    }
+
+    // Steps until the machine blocks, stops or halts
+    pub fn run(&mut self) -> Result<State, Error> {
+        loop {
+            let state = self.step()?;
+            if state != State::Running {
+                return Ok(state);
+            }
+        }
+    }
+}
+
+// Runs two message passing machines, delivering what one sends to the other,
+// until both are halted or waiting for a message that won't come
+pub fn run_pair(vms: &mut [Vm<Mailbox>; 2]) -> Result<(), Error> {
+    loop {
+        let mut progress = false;
+        for id in 0..2 {
+            let steps = vms[id].steps();
+            vms[id].run()?;
+            progress |= vms[id].steps() > steps;
+            for v in vms[id].io_mut().take_outbox() {
+                vms[1 - id].io_mut().deliver(v);
+            }
+        }
+        if !progress {
+            return Ok(());
+        }
+    }
+}
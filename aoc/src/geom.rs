@@ -0,0 +1,219 @@
+// Coordinate vectors for square grids and space. Two dimensional vectors are
+// (y, x) with y growing downwards, like grid positions. Hex grids live in the
+// hex module.
+
+use std::fmt;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::BitAnd;
+use std::ops::Div;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::str::FromStr;
+
+use Error;
+
+// The signed integer types coordinates can be made of
+pub trait Num: Copy + Default + Ord + fmt::Debug + From<i8>
+    + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self>
+    + BitAnd<Output = Self> + Neg<Output = Self>
+{
+    fn abs(self) -> Self;
+    fn to_f64(self) -> f64;
+    // Rounds towards zero, saturating at the ends of the type
+    fn from_f64(x: f64) -> Self;
+}
+
+macro_rules! num {
+    ($($t:ty)*) => {
+        $(
+            impl Num for $t {
+                fn abs(self) -> $t {
+                    <$t>::abs(self)
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn from_f64(x: f64) -> $t {
+                    x as $t
+                }
+            }
+        )*
+    }
+}
+
+num!(i8 i16 i32 i64 i128 isize);
+
+// Implements the operators for a vector type with scalars of type n and the
+// given fields
+macro_rules! ops {
+    ([$($g:tt)*] $v:ty, $n:ty, $($f:ident),*) => {
+        impl<$($g)*> Add for $v {
+            type Output = $v;
+            fn add(self, other: $v) -> $v {
+                Self{$($f: self.$f + other.$f),*}
+            }
+        }
+
+        impl<$($g)*> Sub for $v {
+            type Output = $v;
+            fn sub(self, other: $v) -> $v {
+                Self{$($f: self.$f - other.$f),*}
+            }
+        }
+
+        impl<$($g)*> Neg for $v {
+            type Output = $v;
+            fn neg(self) -> $v {
+                Self{$($f: -self.$f),*}
+            }
+        }
+
+        impl<$($g)*> Mul<$n> for $v {
+            type Output = $v;
+            fn mul(self, n: $n) -> $v {
+                Self{$($f: self.$f * n),*}
+            }
+        }
+
+        impl<$($g)*> AddAssign for $v {
+            fn add_assign(&mut self, other: $v) {
+                *self = *self + other;
+            }
+        }
+
+        impl<$($g)*> SubAssign for $v {
+            fn sub_assign(&mut self, other: $v) {
+                *self = *self - other;
+            }
+        }
+    }
+}
+
+#[derive(Debug,Copy,Clone,Default,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Vec2<T> {
+    pub y: T,
+    pub x: T,
+}
+
+ops!([T: Num] Vec2<T>, T, y, x);
+
+impl<T: Num> Vec2<T> {
+    pub fn new(y: T, x: T) -> Vec2<T> {
+        Vec2{y, x}
+    }
+
+    pub fn manhattan(self) -> T {
+        self.y.abs() + self.x.abs()
+    }
+
+    // Turned right, that is clockwise on screen
+    pub fn rr(self) -> Vec2<T> {
+        Vec2::new(self.x, -self.y)
+    }
+
+    // Turned left, that is counterclockwise on screen
+    pub fn rl(self) -> Vec2<T> {
+        Vec2::new(-self.x, self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((y, x): (T, T)) -> Vec2<T> {
+        Vec2{y, x}
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(v: Vec2<T>) -> (T, T) {
+        (v.y, v.x)
+    }
+}
+
+#[derive(Debug,Copy,Clone,Default,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+ops!([T: Num] Vec3<T>, T, x, y, z);
+
+impl<T: Num> Vec3<T> {
+    pub fn new(x: T, y: T, z: T) -> Vec3<T> {
+        Vec3{x, y, z}
+    }
+
+    pub fn manhattan(self) -> T {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Vec3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{},{},{}>", self.x, self.y, self.z)
+    }
+}
+
+// Parses "<x,y,z>", the brackets and spaces around the numbers are optional
+impl<T> FromStr for Vec3<T>
+    where T: FromStr,
+          T::Err: fmt::Display,
+{
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Vec3<T>, Error> {
+        let inner = s.trim();
+        let inner = if inner.starts_with('<') && inner.ends_with('>') && inner.len() >= 2 {
+            &inner[1..inner.len() - 1]
+        } else {
+            inner
+        };
+        let v: Vec<_> = inner.split(',').map(|n| n.trim()).collect();
+        if v.len() != 3 {
+            return Err(Error::token(s, s.trim(), "Expected a vector <x,y,z>"));
+        }
+        Ok(Vec3 {
+            x: ::parse_token(s, v[0])?,
+            y: ::parse_token(s, v[1])?,
+            z: ::parse_token(s, v[2])?,
+        })
+    }
+}
+
+// The four directions on a square grid
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub fn all() -> [Dir4; 4] {
+        [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left]
+    }
+
+    pub fn vec(self) -> Vec2<isize> {
+        match self {
+            Dir4::Up => Vec2::new(-1, 0),
+            Dir4::Right => Vec2::new(0, 1),
+            Dir4::Down => Vec2::new(1, 0),
+            Dir4::Left => Vec2::new(0, -1),
+        }
+    }
+
+    pub fn rr(self) -> Dir4 {
+        Dir4::all()[(self as usize + 1) % 4]
+    }
+
+    pub fn rl(self) -> Dir4 {
+        Dir4::all()[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        Dir4::all()[(self as usize + 2) % 4]
+    }
+}
@@ -0,0 +1,356 @@
+// Graphs over any copyable node type. Nodes are numbered in the order they
+// are first seen and the searches walk neighbours in the order their edges
+// were added, so results don't depend on hashing.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+// Disjoint sets over 0..n
+#[derive(Debug,Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> UnionFind {
+        UnionFind{parent: (0..n).collect(), size: vec![1; n], sets: n}
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    // Adds a new set of its own and returns it
+    pub fn push(&mut self) -> usize {
+        let n = self.parent.len();
+        self.parent.push(n);
+        self.size.push(1);
+        self.sets += 1;
+        n
+    }
+
+    // The representative of the set holding x
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    // Merges the sets of a and b, false if they were the same already
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // The size of the set holding x
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    // The number of disjoint sets
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+}
+
+#[derive(Debug,Clone)]
+pub struct Graph<T> {
+    directed: bool,
+    nodes: Vec<T>,
+    index: HashMap<T, usize>,
+    out: Vec<Vec<usize>>,
+    into: Vec<Vec<usize>>,
+    edges: usize,
+}
+
+impl<T: Eq + Hash + Copy> Graph<T> {
+    // Edges go both ways
+    pub fn undirected() -> Graph<T> {
+        Graph::new(false)
+    }
+
+    pub fn directed() -> Graph<T> {
+        Graph::new(true)
+    }
+
+    fn new(directed: bool) -> Graph<T> {
+        Graph {
+            directed,
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            out: Vec::new(),
+            into: Vec::new(),
+            edges: 0,
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    // The number of nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // The number of edges, an undirected edge counts once
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    pub fn contains(&self, node: T) -> bool {
+        self.index.contains_key(&node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = T> + '_ {
+        self.nodes.iter().cloned()
+    }
+
+    fn id(&mut self, node: T) -> usize {
+        if let Some(&i) = self.index.get(&node) {
+            return i;
+        }
+        let i = self.nodes.len();
+        self.nodes.push(node);
+        self.index.insert(node, i);
+        self.out.push(Vec::new());
+        self.into.push(Vec::new());
+        i
+    }
+
+    pub fn add_node(&mut self, node: T) {
+        self.id(node);
+    }
+
+    // Adds the edge unless it's there already, false if it was
+    pub fn add_edge(&mut self, from: T, to: T) -> bool {
+        let (a, b) = (self.id(from), self.id(to));
+        if self.out[a].contains(&b) {
+            return false;
+        }
+        self.out[a].push(b);
+        self.into[b].push(a);
+        if !self.directed && a != b {
+            self.out[b].push(a);
+            self.into[a].push(b);
+        }
+        self.edges += 1;
+        true
+    }
+
+    pub fn has_edge(&self, from: T, to: T) -> bool {
+        match (self.index.get(&from), self.index.get(&to)) {
+            (Some(&a), Some(b)) => self.out[a].contains(b),
+            _ => false,
+        }
+    }
+
+    // The nodes reachable over a single edge
+    pub fn neighbours(&self, node: T) -> impl Iterator<Item = T> + '_ {
+        let out = self.index.get(&node).map(|&i| &self.out[i][..]).unwrap_or(&[]);
+        out.iter().map(move |&j| self.nodes[j])
+    }
+
+    pub fn out_degree(&self, node: T) -> usize {
+        self.index.get(&node).map_or(0, |&i| self.out[i].len())
+    }
+
+    pub fn in_degree(&self, node: T) -> usize {
+        self.index.get(&node).map_or(0, |&i| self.into[i].len())
+    }
+
+    // The number of edges touching node, for directed graphs those going in
+    // and out
+    pub fn degree(&self, node: T) -> usize {
+        if self.directed {
+            self.in_degree(node) + self.out_degree(node)
+        } else {
+            self.out_degree(node)
+        }
+    }
+
+    // Breadth first from start, along with the number of steps to get there
+    pub fn bfs(&self, start: T) -> Bfs<'_, T> {
+        let mut seen = vec![false; self.len()];
+        let mut queue = VecDeque::new();
+        if let Some(&i) = self.index.get(&start) {
+            seen[i] = true;
+            queue.push_back((i, 0));
+        }
+        Bfs{graph: self, seen, queue}
+    }
+
+    // Depth first from start, each node as it is first entered
+    pub fn dfs(&self, start: T) -> Dfs<'_, T> {
+        let stack = self.index.get(&start).map(|&i| vec![i]).unwrap_or_default();
+        Dfs{graph: self, seen: vec![false; self.len()], stack}
+    }
+
+    // The number of steps to every node reachable from start
+    pub fn distances(&self, start: T) -> HashMap<T, usize> {
+        self.bfs(start).collect()
+    }
+
+    // The nodes reachable from start, start included
+    pub fn reachable(&self, start: T) -> HashSet<T> {
+        self.bfs(start).map(|(n, _)| n).collect()
+    }
+
+    // A shortest path from one node to another, both ends included
+    pub fn shortest_path(&self, from: T, to: T) -> Option<Vec<T>> {
+        let (&a, &b) = (self.index.get(&from)?, self.index.get(&to)?);
+        let mut prev = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        prev[a] = Some(a);
+        queue.push_back(a);
+        while let Some(n) = queue.pop_front() {
+            if n == b {
+                let mut path = vec![self.nodes[b]];
+                let mut n = b;
+                while n != a {
+                    n = prev[n].unwrap();
+                    path.push(self.nodes[n]);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &m in &self.out[n] {
+                if prev[m].is_none() {
+                    prev[m] = Some(n);
+                    queue.push_back(m);
+                }
+            }
+        }
+        None
+    }
+
+    // The connected components, ignoring the direction of edges
+    pub fn components(&self) -> Components<T> {
+        let mut sets = UnionFind::new(self.len());
+        for (a, out) in self.out.iter().enumerate() {
+            for &b in out {
+                sets.union(a, b);
+            }
+        }
+        let mut labels = HashMap::with_capacity(self.len());
+        let mut roots = HashMap::new();
+        let mut sizes = Vec::new();
+        for (i, &node) in self.nodes.iter().enumerate() {
+            let root = sets.find(i);
+            let label = *roots.entry(root).or_insert_with(|| {
+                sizes.push(sets.size[root]);
+                sizes.len() - 1
+            });
+            labels.insert(node, label);
+        }
+        Components{labels, sizes}
+    }
+}
+
+pub struct Bfs<'a, T: 'a> {
+    graph: &'a Graph<T>,
+    seen: Vec<bool>,
+    queue: VecDeque<(usize, usize)>,
+}
+
+impl<'a, T: Eq + Hash + Copy> Iterator for Bfs<'a, T> {
+    type Item = (T, usize);
+
+    fn next(&mut self) -> Option<(T, usize)> {
+        let (n, d) = self.queue.pop_front()?;
+        for &m in &self.graph.out[n] {
+            if !self.seen[m] {
+                self.seen[m] = true;
+                self.queue.push_back((m, d + 1));
+            }
+        }
+        Some((self.graph.nodes[n], d))
+    }
+}
+
+pub struct Dfs<'a, T: 'a> {
+    graph: &'a Graph<T>,
+    seen: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl<'a, T: Eq + Hash + Copy> Iterator for Dfs<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while let Some(n) = self.stack.pop() {
+            if self.seen[n] {
+                continue;
+            }
+            self.seen[n] = true;
+            // Reversed so the first neighbour is entered first
+            for &m in self.graph.out[n].iter().rev() {
+                if !self.seen[m] {
+                    self.stack.push(m);
+                }
+            }
+            return Some(self.graph.nodes[n]);
+        }
+        None
+    }
+}
+
+// Which component every node is in. Components are labelled from 0 in the
+// order their first node was added to the graph.
+#[derive(Debug,Clone)]
+pub struct Components<T> {
+    labels: HashMap<T, usize>,
+    sizes: Vec<usize>,
+}
+
+impl<T: Eq + Hash + Copy> Components<T> {
+    // The number of components
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn label(&self, node: T) -> Option<usize> {
+        self.labels.get(&node).cloned()
+    }
+
+    pub fn labels(&self) -> &HashMap<T, usize> {
+        &self.labels
+    }
+
+    // The number of nodes in each component
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    pub fn members(&self, label: usize) -> HashSet<T> {
+        self.labels.iter().filter(|&(_, &l)| l == label).map(|(&n, _)| n).collect()
+    }
+}
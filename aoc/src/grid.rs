@@ -0,0 +1,505 @@
+// A dense two dimensional grid stored row by row. Positions are (y, x) pairs
+// with y growing downwards.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt;
+use std::iter;
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::iter::StepBy;
+use std::slice;
+use std::str::FromStr;
+
+use Error;
+
+#[derive(Debug,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1), (-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1),
+];
+
+// Parses a row of cells. The row has to be a slice of s, so that errors point
+// into s.
+fn parse_row<T, F>(s: &str, row: &str, f: &mut F) -> Result<Vec<T>, Error>
+    where F: FnMut(char) -> Option<T>
+{
+    row.char_indices()
+        .map(|(i, c)| f(c).ok_or_else(|| {
+            Error::token(s, &row[i..i + c.len_utf8()], "Invalid cell")
+        }))
+        .collect()
+}
+
+// A cell of a picture of # and ., as the FromStr of Grid<bool> parses them
+pub fn parse_cell(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+impl<T> Grid<T> {
+    // A grid from its cells in row order
+    pub fn from_vec(height: usize, width: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if height * width != cells.len() {
+            return None;
+        }
+        Some(Grid{height, width, cells})
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, Error> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        if let Some(y) = rows.iter().position(|r| r.len() != width) {
+            return Err(Error::from("Expected equal widths").at_line(y + 1));
+        }
+        Ok(Grid{height, width, cells: rows.into_iter().flatten().collect()})
+    }
+
+    // Parses rows of text, turning every character into a cell with f. The
+    // rows have to be slices of s, so that errors point into s.
+    pub fn parse_rows<'a, I, F>(s: &'a str, rows: I, mut f: F) -> Result<Grid<T>, Error>
+        where I: IntoIterator<Item = &'a str>,
+              F: FnMut(char) -> Option<T>
+    {
+        let mut grid = Grid{height: 0, width: 0, cells: Vec::new()};
+        for row in rows {
+            let cells = parse_row(s, row, &mut f)?;
+            if grid.height == 0 {
+                grid.width = cells.len();
+            } else if cells.len() != grid.width {
+                return Err(Error::token(s, row, "Expected equal widths"));
+            }
+            grid.cells.extend(cells);
+            grid.height += 1;
+        }
+        if grid.cells.is_empty() {
+            return Err(Error::token(s, s, "Empty grid"));
+        }
+        Ok(grid)
+    }
+
+    // Parses one row per line. Blank lines before and after the grid are
+    // ignored, but whitespace within the rows is kept.
+    pub fn parse_with<F>(s: &str, mut f: F) -> Result<Grid<T>, Error>
+        where F: FnMut(char) -> Option<T>
+    {
+        let lines: Vec<_> = s.lines().map(|l| l.trim_end_matches('\r')).collect();
+        let first = lines.iter().position(|l| !l.trim().is_empty())
+            .ok_or_else(|| Error::from("Empty grid"))?;
+        let last = lines.iter().rposition(|l| !l.trim().is_empty()).unwrap();
+
+        let mut rows = Vec::new();
+        for (n, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+            rows.push(parse_row(line, line, &mut f).map_err(|e| e.at_line(n + 1))?);
+            if rows[0].len() != rows[rows.len() - 1].len() {
+                return Err(Error::token(line, line, "Expected equal widths").at_line(n + 1));
+            }
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The position, if (y, x) lies within the grid
+    pub fn in_bounds(&self, y: isize, x: isize) -> Option<(usize, usize)> {
+        if 0 <= y && y < self.height as isize && 0 <= x && x < self.width as isize {
+            Some((y as usize, x as usize))
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, (y, x): (usize, usize)) -> Option<&T> {
+        if y < self.height && x < self.width {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (y, x): (usize, usize)) -> Option<&mut T> {
+        if y < self.height && x < self.width {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    // Like get, but with signed coordinates that may point off the grid
+    pub fn at(&self, y: isize, x: isize) -> Option<&T> {
+        self.get(self.in_bounds(y, x)?)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        if y < self.height {
+            Some(&mut self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> Option<StepBy<slice::Iter<'_, T>>> {
+        if x < self.width {
+            Some(self.cells[x..].iter().step_by(self.width))
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    // Every cell together with its position
+    pub fn positions<'a>(&'a self) -> impl Iterator<Item = ((usize, usize), &'a T)> + 'a {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, c)| ((i / width, i % width), c))
+    }
+
+    fn offsets<'a>(&self, (y, x): (usize, usize), offsets: &'a [(isize, isize)])
+        -> impl Iterator<Item = (usize, usize)> + 'a
+    {
+        let (height, width) = (self.height as isize, self.width as isize);
+        let (y, x) = (y as isize, x as isize);
+        offsets.iter()
+            .map(move |&(dy, dx)| (y + dy, x + dx))
+            .filter(move |&(y, x)| 0 <= y && y < height && 0 <= x && x < width)
+            .map(|(y, x)| (y as usize, x as usize))
+    }
+
+    // The positions above, right, below and left of p that are on the grid
+    pub fn neighbours4(&self, p: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(p, &NEIGHBOURS4)
+    }
+
+    // Like neighbours4, including the diagonals
+    pub fn neighbours8(&self, p: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.offsets(p, &NEIGHBOURS8)
+    }
+
+    // Builds a grid of the same size from every cell and its position
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+        where F: FnMut((usize, usize), &T) -> U
+    {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.positions().map(|(p, c)| f(p, c)).collect(),
+        }
+    }
+
+    // One character per cell and a newline after every row
+    pub fn render<F: FnMut(&T) -> char>(&self, mut f: F) -> String {
+        let mut s = String::with_capacity(self.height * (self.width + 1));
+        for row in self.rows() {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(height: usize, width: usize, value: T) -> Grid<T> {
+        Grid{height, width, cells: vec![value; height * width]}
+    }
+
+    // Rotated by 90 degrees clockwise
+    pub fn rotate(&self) -> Grid<T> {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for x in 0..self.width {
+            for y in (0..self.height).rev() {
+                cells.push(self[(y, x)].clone());
+            }
+        }
+        Grid{height: self.width, width: self.height, cells}
+    }
+
+    // Mirrored left to right
+    pub fn flip(&self) -> Grid<T> {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for row in self.rows() {
+            cells.extend(row.iter().rev().cloned());
+        }
+        Grid{height: self.height, width: self.width, cells}
+    }
+
+    // The four rotations of the grid and of its mirror image
+    pub fn orientations(&self) -> Vec<Grid<T>> {
+        let mut ret = Vec::with_capacity(8);
+        for g in &[self.clone(), self.flip()] {
+            let mut g = g.clone();
+            for _ in 0..4 {
+                let next = g.rotate();
+                ret.push(g);
+                g = next;
+            }
+        }
+        ret
+    }
+
+    // A copy of the height x width cells starting at (y, x)
+    pub fn sub_grid(&self, (y, x): (usize, usize), height: usize, width: usize) -> Option<Grid<T>> {
+        if y + height > self.height || x + width > self.width {
+            return None;
+        }
+        let mut cells = Vec::with_capacity(height * width);
+        for row in self.rows().skip(y).take(height) {
+            cells.extend_from_slice(&row[x..x + width]);
+        }
+        Some(Grid{height, width, cells})
+    }
+
+    // Copies other into the grid with its top left corner at (y, x). Nothing
+    // is copied if other doesn't fit.
+    pub fn splice(&mut self, (y, x): (usize, usize), other: &Grid<T>) -> Option<()> {
+        if y + other.height > self.height || x + other.width > self.width {
+            return None;
+        }
+        for (i, src) in other.rows().enumerate() {
+            let start = (y + i) * self.width + x;
+            self.cells[start..start + other.width].clone_from_slice(src);
+        }
+        Some(())
+    }
+
+    // The grid with n cells of value added on every side
+    pub fn pad(&self, n: usize, value: T) -> Grid<T> {
+        let mut ret = Grid::new(self.height + 2 * n, self.width + 2 * n, value);
+        ret.splice((n, n), self).unwrap();
+        ret
+    }
+
+    // Every cell blown up to an n by n square
+    pub fn scale(&self, n: usize) -> Grid<T> {
+        let mut cells = Vec::with_capacity(self.len() * n * n);
+        for row in self.rows() {
+            let wide: Vec<T> = row.iter().flat_map(|c| iter::repeat_n(c.clone(), n)).collect();
+            for _ in 0..n {
+                cells.extend_from_slice(&wide);
+            }
+        }
+        Grid{height: self.height * n, width: self.width * n, cells}
+    }
+}
+
+impl Grid<bool> {
+    // Parses '#' as true and '.' as false
+    pub fn parse(s: &str) -> Result<Grid<bool>, Error> {
+        Grid::parse_with(s, parse_cell)
+    }
+}
+
+impl FromStr for Grid<bool> {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Grid<bool>, Error> {
+        Grid::parse(s)
+    }
+}
+
+impl fmt::Display for Grid<bool> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render(|&c| if c { '#' } else { '.' }))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+    fn index(&self, p: (usize, usize)) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("{:?} is outside of a {}x{} grid", p, self.height, self.width))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, p: (usize, usize)) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        self.get_mut(p).unwrap_or_else(|| panic!("{:?} is outside of a {}x{} grid", p, height, width))
+    }
+}
+
+// The smallest rectangle holding a set of positions, with inclusive corners
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub struct Bounds {
+    pub min: (isize, isize),
+    pub max: (isize, isize),
+}
+
+impl Bounds {
+    pub fn new(p: (isize, isize)) -> Bounds {
+        Bounds{min: p, max: p}
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    pub fn contains(&self, (y, x): (isize, isize)) -> bool {
+        self.min.0 <= y && y <= self.max.0 && self.min.1 <= x && x <= self.max.1
+    }
+
+    pub fn extend(&mut self, (y, x): (isize, isize)) {
+        self.min = (self.min.0.min(y), self.min.1.min(x));
+        self.max = (self.max.0.max(y), self.max.1.max(x));
+    }
+}
+
+const CHUNK_BITS: usize = 6;
+const CHUNK_SIZE: isize = 1 << CHUNK_BITS;
+
+fn chunk_key((y, x): (isize, isize)) -> (isize, isize) {
+    (y >> CHUNK_BITS, x >> CHUNK_BITS)
+}
+
+fn chunk_offset((y, x): (isize, isize)) -> usize {
+    (((y & (CHUNK_SIZE - 1)) << CHUNK_BITS) | (x & (CHUNK_SIZE - 1))) as usize
+}
+
+// An unbounded grid with signed positions. Cells are allocated in square
+// chunks the first time they are written, every other cell holds the default
+// value. The bounds cover every position written so far.
+#[derive(Debug,Clone)]
+pub struct Sparse<T> {
+    default: T,
+    index: HashMap<(isize, isize), usize>,
+    chunks: Vec<Vec<T>>,
+    // The most recently used chunk, as walks tend to stay in one place
+    last: Cell<Option<((isize, isize), usize)>>,
+    bounds: Option<Bounds>,
+}
+
+impl<T: Clone> Sparse<T> {
+    pub fn new(default: T) -> Sparse<T> {
+        Sparse {
+            default,
+            index: HashMap::new(),
+            chunks: Vec::new(),
+            last: Cell::new(None),
+            bounds: None,
+        }
+    }
+
+    // A sparse copy of a dense grid with its top left corner at origin
+    pub fn from_grid(grid: &Grid<T>, origin: (isize, isize), default: T) -> Sparse<T> {
+        let mut ret = Sparse::new(default);
+        for ((y, x), c) in grid.positions() {
+            ret.set((origin.0 + y as isize, origin.1 + x as isize), c.clone());
+        }
+        ret
+    }
+
+    fn chunk(&self, key: (isize, isize)) -> Option<usize> {
+        match self.last.get() {
+            Some((k, i)) if k == key => Some(i),
+            _ => {
+                let i = *self.index.get(&key)?;
+                self.last.set(Some((key, i)));
+                Some(i)
+            }
+        }
+    }
+
+    pub fn get(&self, p: (isize, isize)) -> &T {
+        match self.chunk(chunk_key(p)) {
+            Some(i) => &self.chunks[i][chunk_offset(p)],
+            None => &self.default,
+        }
+    }
+
+    pub fn get_mut(&mut self, p: (isize, isize)) -> &mut T {
+        match self.bounds {
+            Some(ref mut b) => b.extend(p),
+            None => self.bounds = Some(Bounds::new(p)),
+        }
+
+        let key = chunk_key(p);
+        let i = match self.chunk(key) {
+            Some(i) => i,
+            None => {
+                let i = self.chunks.len();
+                self.chunks.push(vec![self.default.clone(); (CHUNK_SIZE * CHUNK_SIZE) as usize]);
+                self.index.insert(key, i);
+                self.last.set(Some((key, i)));
+                i
+            }
+        };
+        &mut self.chunks[i][chunk_offset(p)]
+    }
+
+    pub fn set(&mut self, p: (isize, isize), value: T) {
+        *self.get_mut(p) = value;
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    // The positions and cells of every allocated chunk that lie within the
+    // bounds, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> {
+        let bounds = self.bounds;
+        self.index.iter()
+            .flat_map(move |(&(cy, cx), &i)| {
+                self.chunks[i].iter().enumerate().map(move |(offset, c)| {
+                    let y = (cy << CHUNK_BITS) + (offset >> CHUNK_BITS) as isize;
+                    let x = (cx << CHUNK_BITS) + (offset as isize & (CHUNK_SIZE - 1));
+                    ((y, x), c)
+                })
+            })
+            .filter(move |&(p, _)| bounds.is_some_and(|b| b.contains(p)))
+    }
+
+    // A dense copy of the cells within the bounds
+    pub fn to_grid(&self) -> Option<Grid<T>> {
+        let b = self.bounds?;
+        let mut cells = Vec::with_capacity(b.height() * b.width());
+        for y in b.min.0..=b.max.0 {
+            for x in b.min.1..=b.max.1 {
+                cells.push(self.get((y, x)).clone());
+            }
+        }
+        Grid::from_vec(b.height(), b.width(), cells)
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt;
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::slice::Iter;
+use std::str::FromStr;
+
+use Error;
+
+// 64 bit FNV-1a, a stable fingerprint for puzzle inputs
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3))
+}
+
+// CRC-32 as used by PNG and gzip
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &b| {
+        (0..8).fold(crc ^ u32::from(b), |c, _| if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 })
+    })
+}
+
+// The Adler-32 checksum closing a zlib stream
+pub fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &c| {
+        let a = (a + u32::from(c)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+// The lengths appended to the input of every full knot hash
+pub const SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
+
+// The example hashes from the puzzle text of day 10
+pub const EXAMPLES: [(&str, &str); 4] = [
+    ("", "a2582a3a0e66e6e86e3812dcb672a272"),
+    ("AoC 2017", "33efeb34ea91902bb2f59c9920caa6cd"),
+    ("1,2,3", "3efbe78a8d82f29979031a4aa0b16a9d"),
+    ("1,2,4", "63960835bcdc130f0b66d7ff4f6a5a8e"),
+];
+
+// Reverses length elements of the ring starting at pos, wrapping around
+fn reverse_wrapped(s: &mut [u8], pos: usize, length: usize) {
+    if length == 0 {
+        return;
+    }
+    let n = s.len();
+    let mut l = pos;
+    let mut r = pos + length - 1;
+
+    while l < r {
+        s.swap(l % n, r % n);
+
+        l += 1;
+        r -= 1;
+    }
+}
+
+// The knot hash of day 10. Input is collected as it is written and the ring
+// is tied when asking for the result, so the hasher can be reused to hash
+// longer input. By default it is the full hash over a ring of 256 with 64
+// rounds and the standard suffix. Lengths can't be longer than the ring.
+// Used as a Hasher, bytes that don't fit can't be reported by write, so
+// finish gives 0 for all such input. Check dense or sparse for the error
+// when hashing with a smaller ring, it only can't happen on the full one.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct KnotHasher {
+    size: usize,
+    rounds: usize,
+    suffix: Vec<usize>,
+    lengths: Vec<usize>,
+    // A length written through Hasher that didn't fit, reported when tying
+    error: Option<Error>,
+}
+
+impl Default for KnotHasher {
+    fn default() -> KnotHasher {
+        KnotHasher::new()
+    }
+}
+
+impl KnotHasher {
+    pub fn new() -> KnotHasher {
+        KnotHasher {
+            size: 256,
+            rounds: 64,
+            suffix: SUFFIX.iter().map(|&l| l as usize).collect(),
+            lengths: Vec::new(),
+            error: None,
+        }
+    }
+
+    // A single round without suffix, as in the first part of day 10
+    pub fn single_round() -> KnotHasher {
+        KnotHasher{rounds: 1, suffix: Vec::new(), ..KnotHasher::new()}
+    }
+
+    fn check(&self, length: usize) -> Result<(), Error> {
+        if length > self.size {
+            return Err(Error::from(format!("Length {} is longer than the list", length)));
+        }
+        Ok(())
+    }
+
+    // The number of marks on the ring, from 1 to 256. The suffix and the
+    // lengths so far have to fit.
+    pub fn size(mut self, size: usize) -> Result<KnotHasher, Error> {
+        if !(1..=256).contains(&size) {
+            return Err(Error::from(format!("Ring sizes go from 1 to 256, not {}", size)));
+        }
+        self.size = size;
+        for &l in self.suffix.iter().chain(&self.lengths) {
+            self.check(l)?;
+        }
+        Ok(self)
+    }
+
+    pub fn rounds(mut self, rounds: usize) -> KnotHasher {
+        self.rounds = rounds;
+        self
+    }
+
+    pub fn suffix(mut self, suffix: &[u8]) -> Result<KnotHasher, Error> {
+        for &l in suffix {
+            self.check(l as usize)?;
+        }
+        self.suffix = suffix.iter().map(|&l| l as usize).collect();
+        Ok(self)
+    }
+
+    // Every byte is a length. Nothing is added if one of them is too long.
+    pub fn update(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for &b in bytes {
+            self.check(b as usize)?;
+        }
+        self.lengths.extend(bytes.iter().map(|&b| b as usize));
+        Ok(())
+    }
+
+    // Adds a length directly, which can be up to the size of the ring
+    pub fn push_length(&mut self, length: usize) -> Result<(), Error> {
+        self.check(length)?;
+        self.lengths.push(length);
+        Ok(())
+    }
+
+    fn tie(&self) -> Vec<u8> {
+        let mut ring: Vec<u8> = (0..self.size).map(|i| i as u8).collect();
+        let mut pos = 0;
+        let mut skip = 0;
+
+        for _ in 0..self.rounds {
+            for &l in self.lengths.iter().chain(&self.suffix) {
+                reverse_wrapped(&mut ring, pos, l);
+
+                pos = (pos + l + skip) % self.size;
+                skip = (skip + 1) % self.size;
+            }
+        }
+
+        ring
+    }
+
+    fn fold(sparse: &[u8]) -> Knot {
+        Knot(sparse.chunks(16).map(|chunk| chunk.iter().fold(0, |x, c| x ^ c)).collect())
+    }
+
+    // The ring after all rounds
+    pub fn sparse(&self) -> Result<Vec<u8>, Error> {
+        match self.error {
+            Some(ref e) => Err(e.clone()),
+            None => Ok(self.tie()),
+        }
+    }
+
+    // The ring folded down by xoring blocks of 16
+    pub fn dense(&self) -> Result<Knot, Error> {
+        self.sparse().map(|sparse| KnotHasher::fold(&sparse))
+    }
+}
+
+impl Hasher for KnotHasher {
+    // A Hasher can't fail, so bytes too long for a smaller ring are left out
+    // and the first of them is reported by sparse and dense
+    fn write(&mut self, bytes: &[u8]) {
+        if let Err(e) = self.update(bytes) {
+            self.error.get_or_insert(e);
+        }
+    }
+
+    // The first eight bytes of the dense hash, 0 if some bytes were left out
+    fn finish(&self) -> u64 {
+        self.dense().map_or(0, |k| k.iter().take(8).fold(0, |h, &b| h << 8 | u64::from(b)))
+    }
+}
+
+// Builds full knot hashers, to use them for hash maps. Every byte fits on
+// the full ring, so finish never falls back to 0 for these.
+#[derive(Debug,Clone,Copy,Default)]
+pub struct BuildKnotHasher;
+
+impl BuildHasher for BuildKnotHasher {
+    type Hasher = KnotHasher;
+
+    fn build_hasher(&self) -> KnotHasher {
+        KnotHasher::new()
+    }
+}
+
+// A dense knot hash, 16 bytes for the full hash
+#[derive(Debug,Clone,PartialEq,Eq,Hash)]
+pub struct Knot(Vec<u8>);
+
+impl Knot {
+    // The full knot hash of bytes
+    pub fn hash(bytes: &[u8]) -> Knot {
+        // Every byte fits on the full ring
+        let h = KnotHasher{lengths: bytes.iter().map(|&b| b as usize).collect(), ..KnotHasher::new()};
+        KnotHasher::fold(&h.tie())
+    }
+
+    pub fn iter(&self) -> Iter<'_, u8> {
+        self.0.iter()
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len_bits(&self) -> usize {
+        self.0.len() * 8
+    }
+
+    // Bit i, counting from the most significant bit of the first byte
+    pub fn bit(&self, i: usize) -> bool {
+        self.0[i / 8] & (0x80 >> (i % 8)) != 0
+    }
+
+    pub fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len_bits()).map(move |i| self.bit(i))
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.0.iter().map(|b| b.count_ones()).sum()
+    }
+
+    // The bits as 0 and 1
+    pub fn to_binary(&self) -> String {
+        self.0.iter().map(|b| format!("{:08b}", b)).collect()
+    }
+
+    // The bits as a row of used (#) and free (.) squares, as in day 14
+    pub fn to_grid(&self) -> String {
+        self.bits().map(|b| if b { '#' } else { '.' }).collect()
+    }
+}
+
+impl fmt::Display for Knot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.iter() {
+            write!(f, "{:02x}", c)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Knot {
+    type Err = Error;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Ok(Knot::hash(input.as_bytes()))
+    }
+}
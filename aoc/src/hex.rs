@@ -0,0 +1,267 @@
+// Flat topped hex grids. Positions are kept in cube coordinates where
+// x + y + z == 0, with n and s being neighbours. Axial coordinates drop y and
+// offset coordinates lay the columns out on a square grid, shifting every
+// other column by half a hex. Coordinates are any of the signed integer types
+// of geom.
+
+use std::fmt;
+use std::iter;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::str::FromStr;
+
+use geom::Num;
+use Error;
+
+#[derive(Debug,Copy,Clone,Default,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Hex<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+ops!([T: Num] Hex<T>, T, x, y, z);
+
+// Axial coordinates, q is the column and r grows towards s
+#[derive(Debug,Copy,Clone,Default,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Axial<T> {
+    pub q: T,
+    pub r: T,
+}
+
+// Which columns are shifted down by half a hex in offset coordinates
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash)]
+pub enum Layout {
+    OddQ,
+    EvenQ,
+}
+
+#[derive(Debug,Copy,Clone,Default,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub struct Offset<T> {
+    pub col: T,
+    pub row: T,
+}
+
+impl<T: Num> Hex<T> {
+    pub fn new(x: T, y: T, z: T) -> Hex<T> {
+        debug_assert_eq!(x + y + z, T::default(), "Cube coordinates have to add up to 0");
+        Hex{x, y, z}
+    }
+
+    // The number of steps to the origin
+    pub fn distance(self) -> T {
+        (self.x.abs() + self.y.abs() + self.z.abs()) / T::from(2)
+    }
+
+    pub fn distance_to(self, other: Hex<T>) -> T {
+        (other - self).distance()
+    }
+
+    // Rotated by 60 degrees clockwise around the origin
+    pub fn rr(self) -> Hex<T> {
+        Hex::new(-self.z, -self.x, -self.y)
+    }
+
+    // Rotated by 60 degrees counterclockwise around the origin
+    pub fn rl(self) -> Hex<T> {
+        Hex::new(-self.y, -self.z, -self.x)
+    }
+
+    pub fn step(self, dir: HexDir) -> Hex<T> {
+        self + dir.hex()
+    }
+
+    // The six neighbours, clockwise from north
+    pub fn neighbours(self) -> impl Iterator<Item = Hex<T>> {
+        HexDir::all().to_vec().into_iter().map(move |d| self.step(d))
+    }
+
+    // The hexes at exactly the given distance, clockwise from north. A ring
+    // of radius 0 is just the centre.
+    pub fn ring(self, radius: u32) -> impl Iterator<Item = Hex<T>> {
+        let sides = if radius == 0 { 1 } else { 6 };
+        let len = radius.max(1) as usize;
+        (0..sides).flat_map(move |i| {
+            let corner = (0..radius).fold(self, |h, _| h.step(HexDir::all()[i]));
+            let side = HexDir::all()[i].rr().rr();
+            iter::successors(Some(corner), move |h| Some(h.step(side))).take(len)
+        })
+    }
+
+    // The hexes within the given distance, ring by ring from the centre out
+    pub fn spiral(self, radius: u32) -> impl Iterator<Item = Hex<T>> {
+        iter::once(self).chain((1..radius + 1).flat_map(move |r| self.ring(r)))
+    }
+
+    // The hexes on a straight line to other, both ends included
+    pub fn line(self, other: Hex<T>) -> impl Iterator<Item = Hex<T>> {
+        let n = self.distance_to(other).to_f64();
+        // Nudge the line off the edges between hexes so ties round the same
+        // way in every direction
+        let (ax, ay, az) = (self.x.to_f64() + 1e-6, self.y.to_f64() + 2e-6, self.z.to_f64() - 3e-6);
+        let (bx, by, bz) = (other.x.to_f64() + 1e-6, other.y.to_f64() + 2e-6, other.z.to_f64() - 3e-6);
+        (0..n as u64 + 1).map(move |i| {
+            let t = if n == 0.0 { 0.0 } else { i as f64 / n };
+            round(ax + (bx - ax) * t, ay + (by - ay) * t, az + (bz - az) * t)
+        })
+    }
+
+    // The shortest route to other. There are many of those, this one takes
+    // the needed directions in the order of HexDir::all so the same two
+    // positions always give the same route.
+    pub fn path_to(self, other: Hex<T>) -> Vec<HexDir> {
+        let mut rest = other - self;
+        let mut path = Vec::new();
+        for &d in &HexDir::all() {
+            while (rest - d.hex()).distance() < rest.distance() {
+                rest -= d.hex();
+                path.push(d);
+            }
+        }
+        path
+    }
+
+    // The shortest route back to the origin
+    pub fn path_home(self) -> Vec<HexDir> {
+        self.path_to(Hex::default())
+    }
+
+    pub fn to_axial(self) -> Axial<T> {
+        Axial{q: self.x, r: self.z}
+    }
+
+    pub fn from_axial(a: Axial<T>) -> Hex<T> {
+        Hex::new(a.q, -a.q - a.r, a.r)
+    }
+
+    pub fn to_offset(self, layout: Layout) -> Offset<T> {
+        let (q, r) = (self.x, self.z);
+        let (one, two) = (T::from(1), T::from(2));
+        let row = match layout {
+            Layout::OddQ => r + (q - (q & one)) / two,
+            Layout::EvenQ => r + (q + (q & one)) / two,
+        };
+        Offset{col: q, row}
+    }
+
+    pub fn from_offset(o: Offset<T>, layout: Layout) -> Hex<T> {
+        let q = o.col;
+        let (one, two) = (T::from(1), T::from(2));
+        let r = match layout {
+            Layout::OddQ => o.row - (q - (q & one)) / two,
+            Layout::EvenQ => o.row - (q + (q & one)) / two,
+        };
+        Hex::from_axial(Axial{q, r})
+    }
+}
+
+// Rounds fractional cube coordinates to the hex containing them
+fn round<T: Num>(x: f64, y: f64, z: f64) -> Hex<T> {
+    let (mut rx, mut ry, mut rz) = (x.round(), y.round(), z.round());
+    let (dx, dy, dz) = ((rx - x).abs(), (ry - y).abs(), (rz - z).abs());
+    if dx > dy && dx > dz {
+        rx = -ry - rz;
+    } else if dy > dz {
+        ry = -rx - rz;
+    } else {
+        rz = -rx - ry;
+    }
+    Hex::new(T::from_f64(rx), T::from_f64(ry), T::from_f64(rz))
+}
+
+impl<T: Num> From<Axial<T>> for Hex<T> {
+    fn from(a: Axial<T>) -> Hex<T> {
+        Hex::from_axial(a)
+    }
+}
+
+impl<T: Num> From<Hex<T>> for Axial<T> {
+    fn from(h: Hex<T>) -> Axial<T> {
+        h.to_axial()
+    }
+}
+
+// The six directions on a hex grid, clockwise from north
+#[derive(Debug,Copy,Clone,PartialEq,Eq,Hash,PartialOrd,Ord)]
+pub enum HexDir {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl HexDir {
+    pub fn all() -> [HexDir; 6] {
+        [HexDir::N, HexDir::NE, HexDir::SE, HexDir::S, HexDir::SW, HexDir::NW]
+    }
+
+    pub fn hex<T: Num>(self) -> Hex<T> {
+        let (one, zero) = (T::from(1), T::default());
+        match self {
+            HexDir::N => Hex::new(zero, one, -one),
+            HexDir::NE => Hex::new(one, zero, -one),
+            HexDir::SE => Hex::new(one, -one, zero),
+            HexDir::S => Hex::new(zero, -one, one),
+            HexDir::SW => Hex::new(-one, zero, one),
+            HexDir::NW => Hex::new(-one, one, zero),
+        }
+    }
+
+    pub fn rr(self) -> HexDir {
+        HexDir::all()[(self as usize + 1) % 6]
+    }
+
+    pub fn rl(self) -> HexDir {
+        HexDir::all()[(self as usize + 5) % 6]
+    }
+
+    pub fn reverse(self) -> HexDir {
+        HexDir::all()[(self as usize + 3) % 6]
+    }
+}
+
+impl fmt::Display for HexDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match *self {
+            HexDir::N => "n",
+            HexDir::NE => "ne",
+            HexDir::SE => "se",
+            HexDir::S => "s",
+            HexDir::SW => "sw",
+            HexDir::NW => "nw",
+        })
+    }
+}
+
+impl FromStr for HexDir {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<HexDir, Error> {
+        match s.trim() {
+            "n" => Ok(HexDir::N),
+            "ne" => Ok(HexDir::NE),
+            "se" => Ok(HexDir::SE),
+            "s" => Ok(HexDir::S),
+            "sw" => Ok(HexDir::SW),
+            "nw" => Ok(HexDir::NW),
+            d => Err(Error::token(s, d, "Unexpected direction")),
+        }
+    }
+}
+
+// Parses a comma separated list of directions like "n,ne,sw"
+pub fn parse_dirs(s: &str) -> Result<Vec<HexDir>, Error> {
+    s.trim().split(',')
+        .map(|d| d.parse::<HexDir>().map_err(|e| e.within(s, d)))
+        .collect()
+}
+
+// The inverse of parse_dirs
+pub fn format_dirs(dirs: &[HexDir]) -> String {
+    dirs.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(",")
+}
@@ -0,0 +1,104 @@
+// Writes grids out as images: PBM for black and white, PGM for shades of grey
+// and PNG for colour. The PNG data is stored uncompressed, which keeps the
+// encoder small at the cost of larger files.
+
+use grid::Grid;
+use hash;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+// Binary PBM, with true cells black
+pub fn pbm(grid: &Grid<bool>) -> Vec<u8> {
+    let mut out = format!("P4\n{} {}\n", grid.width(), grid.height()).into_bytes();
+    for row in grid.rows() {
+        for byte in row.chunks(8) {
+            out.push(byte.iter().enumerate().fold(0, |b, (i, &c)| if c { b | 0x80 >> i } else { b }));
+        }
+    }
+    out
+}
+
+// Binary PGM with 256 shades, 0 is black
+pub fn pgm(grid: &Grid<u8>) -> Vec<u8> {
+    let mut out = format!("P5\n{} {}\n255\n", grid.width(), grid.height()).into_bytes();
+    out.extend(grid.iter());
+    out
+}
+
+fn chunk(out: &mut Vec<u8>, kind: &[u8], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = hash::crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+// A zlib stream of stored deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&hash::adler32(data).to_be_bytes());
+    out
+}
+
+// 8 bit RGB PNG
+pub fn png(grid: &Grid<Rgb>) -> Vec<u8> {
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(grid.width() as u32).to_be_bytes());
+    header.extend_from_slice(&(grid.height() as u32).to_be_bytes());
+    // Bit depth, colour type RGB, compression, filter and no interlacing
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+    chunk(&mut out, b"IHDR", &header);
+
+    let mut raw = Vec::with_capacity(grid.height() * (grid.width() * 3 + 1));
+    for row in grid.rows() {
+        // No filter
+        raw.push(0);
+        for pixel in row {
+            raw.extend_from_slice(pixel);
+        }
+    }
+    chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+// A colour for label n that stands out from those of nearby labels, stepping
+// around the colour wheel by the golden angle
+pub fn colour(n: usize) -> Rgb {
+    let hue = (n as f64 * 0.618_033_988_75).fract() * 6.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    // Keep away from white, which is the background
+    let c = |v: f64| (40.0 + v * 180.0) as u8;
+    [c(r), c(g), c(b)]
+}
+
+// A shade of grey for label n, never white
+pub fn grey(n: usize) -> u8 {
+    (n * 73 % 200) as u8
+}
@@ -1,160 +1,741 @@
+#[cfg(feature = "gzip")]
+extern crate flate2;
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::process;
+use std::str::FromStr;
+
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum Error {
+    // Invalid puzzle input. Line and column are counted from 1 and point to
+    // the offending token, if they are known.
+    Parse {
+        line: Option<usize>,
+        column: Option<usize>,
+        token: String,
+        message: String,
+    },
+    Solve(String),
+    Io(String),
+}
+
+impl Error {
+    // An invalid token, which has to be a slice of s. The column is the
+    // position of the token in s.
+    pub fn token<M: Into<String>>(s: &str, token: &str, message: M) -> Error {
+        Error::Parse {
+            line: None,
+            column: None,
+            token: token.to_owned(),
+            message: message.into(),
+        }.within(s, token)
+    }
+
+    // Moves an error found while parsing inner, a slice of s, to its position
+    // in s. An error without a position points to the start of inner.
+    pub fn within(self, s: &str, inner: &str) -> Error {
+        let offset = match offset(s, inner) {
+            Some(offset) => offset,
+            None => return self,
+        };
+        match self {
+            Error::Parse{line, column, token, message} => Error::Parse {
+                line,
+                column: Some(offset + column.unwrap_or(1)),
+                token: if token.is_empty() { inner.to_owned() } else { token },
+                message,
+            },
+            e => e,
+        }
+    }
+
+    // Sets the line number, unless it's already known
+    pub fn at_line(self, n: usize) -> Error {
+        match self {
+            Error::Parse{line, column, token, message} => Error::Parse {
+                line: line.or(Some(n)),
+                column,
+                token,
+                message,
+            },
+            e => e,
+        }
+    }
+}
+
+// Position of inner in s in chars, if inner is a slice of s
+fn offset(s: &str, inner: &str) -> Option<usize> {
+    let start = s.as_ptr() as usize;
+    let pos = inner.as_ptr() as usize;
+    if pos < start || pos + inner.len() > start + s.len() {
+        return None;
+    }
+    Some(s[..pos - start].chars().count())
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Parse{line, column, ref token, ref message} => {
+                write!(f, "Parse error")?;
+                match (line, column) {
+                    (Some(l), Some(c)) => write!(f, " at line {}, column {}", l, c)?,
+                    (Some(l), None) => write!(f, " at line {}", l)?,
+                    (None, Some(c)) => write!(f, " at column {}", c)?,
+                    (None, None) => {},
+                }
+                write!(f, ": {}", message)?;
+                if !token.is_empty() {
+                    write!(f, " \"{}\"", token)?;
+                }
+                Ok(())
+            },
+            Error::Solve(ref e) => write!(f, "Can't solve: {}", e),
+            Error::Io(ref e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Error {
+        Error::Parse {
+            line: None,
+            column: None,
+            token: String::new(),
+            message,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Error {
+    fn from(e: &'a str) -> Error {
+        Error::from(e.to_owned())
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e.to_string())
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Error {
+        Error::from(e.to_string())
+    }
+}
+
+// Parses a token that is a slice of s, errors point to the token
+pub fn parse_token<T>(s: &str, token: &str) -> Result<T, Error>
+    where T: FromStr,
+          T::Err: fmt::Display,
+{
+    token.parse().map_err(|e: T::Err| Error::token(s, token, e.to_string()))
+}
+
+// Parses every non-blank line of the input with f. Errors carry the line
+// number.
+pub fn map_lines<T, F>(input: &str, mut f: F) -> Result<Vec<T>, Error>
+    where F: FnMut(&str) -> Result<T, Error>,
+{
+    input.lines()
+        .enumerate()
+        .filter(|&(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| f(l).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+// Parses the single non-blank line of the input with f
+pub fn map_line<T, F>(input: &str, f: F) -> Result<T, Error>
+    where F: FnMut(&str) -> Result<T, Error>,
+{
+    let mut lines = map_lines(input, f)?;
+    match lines.len() {
+        0 => Err(Error::from("Empty input")),
+        1 => Ok(lines.remove(0)),
+        _ => Err(Error::from("Expected a single line of input")),
+    }
+}
+
+// Parses every non-blank line of the input
+pub fn parse_lines<T>(input: &str) -> Result<Vec<T>, Error>
+    where T: FromStr<Err = Error>,
+{
+    map_lines(input, str::parse)
+}
+
+// The largest r with r * r <= n
+pub(crate) fn isqrt(n: u64) -> u64 {
+    let n = u128::from(n);
+    let mut r = (n as f64).sqrt() as u128;
+    while r * r > n {
+        r -= 1;
+    }
+    while (r + 1) * (r + 1) <= n {
+        r += 1;
+    }
+    r as u64
+}
+
+// One day of the calendar. The puzzle input is parsed once and both parts
+// are solved from the same parsed input.
+pub trait Solution {
+    type Input;
+    type Part1: fmt::Display;
+    type Part2: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error>;
+
+    fn solve(input: &str, part: u32) -> Result<String, Error> {
+        let input = Self::parse(input)?;
+        match part {
+            1 => Self::part1(&input).map(|a| a.to_string()),
+            2 => Self::part2(&input).map(|a| a.to_string()),
+            p => Err(Error::Solve(format!("There is no part {}", p))),
+        }
+    }
+}
+
+// Ends a star binary with the error on stderr, as the runner does
+pub fn or_exit<T>(result: Result<T, Error>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    })
+}
+
+// Parses all of stdin
+pub fn parse_stdin<T>(parse: fn(&str) -> Result<T, Error>) -> Result<T, Error> {
+    input::parse(io::stdin(), parse)
+}
+
+// The main of a star binary. The answer solve gives for the input on stdin
+// goes to stdout, errors reading, parsing or solving to stderr.
+pub fn main<T, A, F>(parse: fn(&str) -> Result<T, Error>, solve: F)
+    where A: fmt::Display,
+          F: FnOnce(&T) -> Result<A, Error>,
+{
+    log::init();
+    let input = or_exit(parse_stdin(parse));
+    println!("{}", or_exit(solve(&input)));
+}
+
+// Diagnostic output on stderr. Nothing below the warning level is shown
+// unless asked for with AOC_LOG or -v flags, so stdout carries the answer
+// alone.
+pub mod log {
+    use std::env;
+    use std::fmt;
+    use std::str::FromStr;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use Error;
+
+    #[derive(Debug,Copy,Clone,PartialEq,Eq,PartialOrd,Ord)]
+    pub enum Level {
+        Error,
+        Warn,
+        Info,
+        Debug,
+        Trace,
+    }
+
+    const LEVELS: [Level; 5] = [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace];
+
+    // The environment variable holding the initial level
+    pub const LEVEL_VAR: &str = "AOC_LOG";
+
+    static LEVEL: AtomicUsize = AtomicUsize::new(Level::Warn as usize);
+
+    pub fn level() -> Level {
+        LEVELS[LEVEL.load(Ordering::Relaxed)]
+    }
+
+    pub fn set_level(level: Level) {
+        LEVEL.store(level as usize, Ordering::Relaxed);
+    }
+
+    pub fn enabled(level: Level) -> bool {
+        level <= self::level()
+    }
+
+    // Raises the level by n steps, saturating at Trace
+    pub fn more(n: usize) {
+        let l = (level() as usize + n).min(Level::Trace as usize);
+        set_level(LEVELS[l]);
+    }
+
+    // The number of steps a verbosity flag like -v, -vv or --trace asks for
+    pub fn flag(arg: &str) -> Option<usize> {
+        match arg {
+            "--verbose" => Some(1),
+            "--trace" => Some(Level::Trace as usize - Level::Warn as usize),
+            _ if arg.len() > 1 && arg.starts_with('-') && arg[1..].bytes().all(|b| b == b'v') => {
+                Some(arg.len() - 1)
+            }
+            _ => None,
+        }
+    }
+
+    // Sets the level from $AOC_LOG and then raises it for every verbosity
+    // flag on the command line
+    pub fn init() {
+        if let Ok(level) = env::var(LEVEL_VAR) {
+            match level.parse() {
+                Ok(level) => set_level(level),
+                Err(e) => eprintln!("Ignoring {}: {}", LEVEL_VAR, e),
+            }
+        }
+        more(env::args().skip(1).filter_map(|a| flag(&a)).sum());
+    }
+
+    pub fn write(level: Level, args: fmt::Arguments) {
+        if enabled(level) {
+            eprintln!("{}", args);
+        }
+    }
+
+    impl fmt::Display for Level {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.pad(match *self {
+                Level::Error => "error",
+                Level::Warn => "warn",
+                Level::Info => "info",
+                Level::Debug => "debug",
+                Level::Trace => "trace",
+            })
+        }
+    }
+
+    impl FromStr for Level {
+        type Err = Error;
+        fn from_str(s: &str) -> Result<Level, Error> {
+            LEVELS.iter()
+                .find(|l| l.to_string().eq_ignore_ascii_case(s.trim()))
+                .cloned()
+                .ok_or_else(|| Error::token(s, s.trim(), "Invalid log level"))
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::log::enabled($level) {
+            $crate::log::write($level, format_args!($($arg)+));
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { log!($crate::log::Level::Info, $($arg)+) }
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { log!($crate::log::Level::Debug, $($arg)+) }
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { log!($crate::log::Level::Trace, $($arg)+) }
+}
+
 pub mod input {
+    use std::fs::File;
     use std::io;
-    use std::io::Stdin;
     use std::io::BufRead;
+    use std::io::BufReader;
     use std::io::Lines;
+    use std::io::Read;
+    use std::path::Path;
+
+    use Error;
 
-    pub struct LineFilter<'a> {
-        lines: Lines<io::StdinLock<'a>>,
+    #[cfg(feature = "gzip")]
+    use flate2::bufread::MultiGzDecoder;
+
+    // Iterates over the non-blank lines of any buffered source
+    pub struct LineFilter<R> {
+        lines: Lines<R>,
     }
 
-    pub fn lines<'a>(stdin: &'a Stdin) -> LineFilter<'a> {
-        LineFilter{lines: stdin.lock().lines()}
+    pub fn lines<R: BufRead>(reader: R) -> LineFilter<R> {
+        LineFilter{lines: reader.lines()}
     }
 
-    impl<'a> Iterator for LineFilter<'a> {
-        type Item = String;
-        fn next(&mut self) -> Option<String> {
+    impl<R: BufRead> Iterator for LineFilter<R> {
+        type Item = io::Result<String>;
+        fn next(&mut self) -> Option<io::Result<String>> {
             self.lines.by_ref()
-                .map(|l| l.unwrap())
-                .skip_while(
-                    |l| l.trim()
-                    .is_empty())
-                .next()
+                .find(|l| match *l {
+                    Ok(ref l) => !l.trim().is_empty(),
+                    Err(_) => true,
+                })
         }
     }
-}
 
-pub mod hash {
-    use std::fmt;
-    use std::slice::Iter;
-    use std::str::FromStr;
+    pub fn all<R: Read>(mut reader: R) -> io::Result<String> {
+        let mut ret = String::new();
+        reader.read_to_string(&mut ret)?;
+        Ok(ret)
+    }
 
-    fn reverse_wrapped(s: &mut[u8], pos: usize, length: usize) {
-        let mut l = pos;
-        let mut r = pos + length - 1;
+    // Reads all of the reader and parses it, failing to read is an I/O error
+    pub fn parse<R: Read, T>(reader: R, parse: fn(&str) -> Result<T, Error>) -> Result<T, Error> {
+        parse(&all(reader)?)
+    }
+
+    // Opens a file, or stdin for "-". Gzip compressed input is detected by its
+    // magic number and decompressed on the fly.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn BufRead>> {
+        let path = path.as_ref();
+        let reader: Box<dyn BufRead> = if path == Path::new("-") {
+            Box::new(BufReader::new(io::stdin()))
+        } else {
+            Box::new(BufReader::new(File::open(path)?))
+        };
+        decompress(reader)
+    }
 
-        while l < r {
-            s.swap(l%256, r%256);
+    // Reads the whole input from a file, or stdin for "-"
+    pub fn read<P: AsRef<Path>>(path: P) -> io::Result<String> {
+        all(open(path)?)
+    }
 
-            l += 1;
-            r -= 1;
+    pub fn decompress<R: BufRead + 'static>(mut reader: R) -> io::Result<Box<dyn BufRead>> {
+        if reader.fill_buf()?.starts_with(&[0x1f, 0x8b]) {
+            gzip(reader)
+        } else {
+            Ok(Box::new(reader))
         }
     }
 
-    fn knot_hash(input: &[u8]) -> [u8; 256] {
-        let mut hash = [0u8; 256];
-        for (i, h) in hash.iter_mut().enumerate() {
-            *h = i as u8;
+    #[cfg(feature = "gzip")]
+    fn gzip<R: BufRead + 'static>(reader: R) -> io::Result<Box<dyn BufRead>> {
+        Ok(Box::new(BufReader::new(MultiGzDecoder::new(reader))))
+    }
+
+    #[cfg(not(feature = "gzip"))]
+    fn gzip<R: BufRead + 'static>(_: R) -> io::Result<Box<dyn BufRead>> {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "Gzip support is not enabled"))
+    }
+}
+
+// Puzzle inputs cached on disk as <dir>/<year>/day<DD>.txt
+pub mod store {
+    use std::env;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    use input;
+    use Error;
+
+    // The environment variable overriding the default directory
+    pub const DIR_VAR: &str = "AOC_INPUTS";
+    pub const DEFAULT_DIR: &str = "inputs";
+
+    pub struct Store {
+        dir: PathBuf,
+    }
+
+    impl Store {
+        pub fn new<P: Into<PathBuf>>(dir: P) -> Store {
+            Store{dir: dir.into()}
         }
 
-        let mut pos = 0;
-        let mut skip = 0;
-        let postfix = [17, 31, 73, 47, 23];
+        // The store in $AOC_INPUTS, or in ./inputs if that isn't set
+        pub fn from_env() -> Store {
+            match env::var_os(DIR_VAR) {
+                Some(ref dir) if !dir.is_empty() => Store::new(dir),
+                _ => Store::new(DEFAULT_DIR),
+            }
+        }
+
+        pub fn dir(&self) -> &Path {
+            &self.dir
+        }
 
-        for _ in 0..64 {
-            for l in input.iter().chain(&postfix) {
-                let l = *l as usize;
-                reverse_wrapped(&mut hash, pos, l);
+        pub fn path(&self, year: u32, day: u32) -> PathBuf {
+            self.dir.join(year.to_string()).join(format!("day{:02}.txt", day))
+        }
 
-                pos = (pos +l + skip) % 256;
-                skip = (skip + 1) % 256;
+        pub fn contains(&self, year: u32, day: u32) -> bool {
+            self.path(year, day).is_file()
+        }
+
+        pub fn read(&self, year: u32, day: u32) -> Result<String, Error> {
+            let path = self.path(year, day);
+            input::read(&path).map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => Error::Io(format!(
+                    "No input for {} day {}, expected it in {}", year, day, path.display())),
+                _ => Error::Io(format!("Can't read {}: {}", path.display(), e)),
+            })
+        }
+
+        pub fn write(&self, year: u32, day: u32, input: &str) -> Result<PathBuf, Error> {
+            let path = self.path(year, day);
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
             }
+            fs::write(&path, input)?;
+            Ok(path)
+        }
+
+        // Copies an input file into the store, replacing the cached one. The
+        // source may be gzip compressed or "-" for stdin.
+        pub fn import<P: AsRef<Path>>(&self, year: u32, day: u32, src: P) -> Result<PathBuf, Error> {
+            let src = src.as_ref();
+            let input = input::read(src)
+                .map_err(|e| Error::Io(format!("Can't read {}: {}", src.display(), e)))?;
+            self.write(year, day, &input)
         }
+    }
+}
+
+// Timing of the parse and solve phases of a solution over repeated runs
+pub mod bench {
+    use std::collections::BTreeMap;
+    use std::fmt;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use Error;
+    use Solution;
 
-        hash
+    #[derive(Debug,Copy,Clone,PartialEq,Eq,PartialOrd,Ord)]
+    pub enum Phase {
+        Parse,
+        Part1,
+        Part2,
     }
 
-    pub struct Knot([u8; 16]);
-    impl fmt::Display for Knot {
+    impl Phase {
+        pub fn all() -> [Phase; 3] {
+            [Phase::Parse, Phase::Part1, Phase::Part2]
+        }
+    }
+
+    impl fmt::Display for Phase {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            for c in self.0.iter() {
-                write!(f, "{:02x}", c)?;
-            }
-            Ok(())
+            f.pad(match *self {
+                Phase::Parse => "parse",
+                Phase::Part1 => "part1",
+                Phase::Part2 => "part2",
+            })
         }
     }
 
-    impl Knot {
-        pub fn iter(&self) -> Iter<u8> {
-            self.0.iter()
+    impl FromStr for Phase {
+        type Err = Error;
+        fn from_str(s: &str) -> Result<Phase, Error> {
+            match s {
+                "parse" => Ok(Phase::Parse),
+                "part1" => Ok(Phase::Part1),
+                "part2" => Ok(Phase::Part2),
+                _ => Err(Error::token(s, s, "Invalid phase")),
+            }
         }
     }
 
-    impl FromStr for Knot {
-        type Err = ();
-        fn from_str(input: &str) -> Result<Self,Self::Err> {
-            let sparse = knot_hash(input.as_bytes());
-            let mut ret = [0u8; 16];
-            for (i, chunk) in sparse.chunks(16).enumerate() {
-                ret[i] = chunk.iter().fold(0, |sum, x| sum ^ x);
+    #[derive(Debug,Copy,Clone,PartialEq,Eq)]
+    pub struct Stats {
+        pub runs: usize,
+        pub min: Duration,
+        pub max: Duration,
+        pub mean: Duration,
+        pub median: Duration,
+        pub stddev: Duration,
+    }
+
+    impl Stats {
+        pub fn new(samples: &[Duration]) -> Option<Stats> {
+            if samples.is_empty() {
+                return None;
             }
-            Ok(Knot(ret))
+
+            let mut sorted = samples.to_vec();
+            sorted.sort();
+            let n = sorted.len();
+            let median = if n % 2 == 1 {
+                sorted[n / 2]
+            } else {
+                (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+            };
+
+            let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+            let mean = nanos.iter().sum::<f64>() / n as f64;
+            let var = nanos.iter().map(|t| (t - mean) * (t - mean)).sum::<f64>() / n as f64;
+
+            Some(Stats {
+                runs: n,
+                min: sorted[0],
+                max: sorted[n - 1],
+                mean: Duration::from_nanos(mean.round() as u64),
+                median,
+                stddev: Duration::from_nanos(var.sqrt().round() as u64),
+            })
         }
     }
-}
 
-pub mod graph {
-    use std::collections::HashMap;
-    use std::collections::HashSet;
-    use std::collections::VecDeque;
-    use std::hash::Hash;
-    use std::fmt::Debug;
+    // The statistics of every phase. A part that fails keeps its error.
+    #[derive(Debug)]
+    pub struct Timings {
+        pub parse: Stats,
+        pub part1: Result<Stats, Error>,
+        pub part2: Result<Stats, Error>,
+    }
 
-    #[derive(Clone)]
-    pub struct Implicit<T>
-    where T: Eq + Hash + Copy + Debug {
-        pub edges: HashMap<T, HashSet<T>>,
+    impl Timings {
+        pub fn get(&self, phase: Phase) -> Result<&Stats, &Error> {
+            match phase {
+                Phase::Parse => Ok(&self.parse),
+                Phase::Part1 => self.part1.as_ref(),
+                Phase::Part2 => self.part2.as_ref(),
+            }
+        }
     }
 
-    impl<T> Implicit<T> 
-    where T: Eq + Hash + Copy + Debug {
-        pub fn new() -> Implicit<T> {
-            Implicit{edges: HashMap::default()}
+    // Times f, which runs at least once
+    pub fn measure<T, F>(runs: usize, mut f: F) -> Result<Stats, Error>
+        where F: FnMut() -> Result<T, Error>
+    {
+        let mut samples = Vec::with_capacity(runs);
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            let result = f();
+            samples.push(start.elapsed());
+            result?;
         }
-        pub fn len(&self) -> usize {
-            self.edges.len()
+        Ok(Stats::new(&samples).unwrap())
+    }
+
+    pub fn run<S: Solution>(input: &str, runs: usize) -> Result<Timings, Error> {
+        let parse = measure(runs, || S::parse(input))?;
+        let input = S::parse(input)?;
+        Ok(Timings {
+            parse,
+            part1: measure(runs, || S::part1(&input)),
+            part2: measure(runs, || S::part2(&input)),
+        })
+    }
+
+    // Saved median times to compare a benchmark against. The text format has
+    // one "<day> <phase> <nanoseconds>" per line.
+    #[derive(Debug,Clone,Default,PartialEq,Eq)]
+    pub struct Baseline {
+        times: BTreeMap<(u32, Phase), Duration>,
+    }
+
+    impl Baseline {
+        pub fn new() -> Baseline {
+            Baseline::default()
         }
-        pub fn insert(&mut self, k: T, v: Option<T>) {
-            let links = self.edges.entry(k).or_insert(HashSet::new());
-            if let Some(v) = v {
-                links.insert(v);
-            }
+
+        pub fn insert(&mut self, day: u32, phase: Phase, time: Duration) {
+            self.times.insert((day, phase), time);
         }
-        pub fn get_any(&self) -> Option<T> {
-            self.edges.keys().next().cloned()
+
+        pub fn get(&self, day: u32, phase: Phase) -> Option<Duration> {
+            self.times.get(&(day, phase)).cloned()
         }
-        pub fn group(&self, seed: T) -> HashSet<T> {
-            let mut visited = HashSet::new();
-            let mut queue = VecDeque::new();
-            queue.push_back(seed);
 
-            while let Some(node) = queue.pop_front() {
-                if !visited.insert(node) {
-                    continue;
-                }
-                if let Some(candidates) = self.edges.get(&node) {
-                    queue.extend(candidates);
+        pub fn add(&mut self, day: u32, timings: &Timings) {
+            for &phase in &Phase::all() {
+                if let Ok(stats) = timings.get(phase) {
+                    self.insert(day, phase, stats.median);
                 }
             }
-
-            visited
         }
-        pub fn num_groups(&self) -> u32 {
-            let mut t = Implicit::<T>{edges: self.edges.clone()};
-            let mut n = 0u32;
-            while let Some(seed) = t.get_any() {
-                let g = t.group(seed);
-                for node in g {
-                    t.edges.remove(&node);
+    }
+
+    impl FromStr for Baseline {
+        type Err = Error;
+        fn from_str(s: &str) -> Result<Baseline, Error> {
+            let mut baseline = Baseline::new();
+            ::map_lines(s, |line| {
+                if line.trim().starts_with('#') {
+                    return Ok(());
+                }
+                let v: Vec<_> = line.split_whitespace().collect();
+                if v.len() != 3 {
+                    return Err(Error::token(line, line.trim(), "Expected \"<day> <phase> <nanoseconds>\""));
                 }
-                n += 1;
+                let day = ::parse_token(line, v[0])?;
+                let phase = v[1].parse().map_err(|e: Error| e.within(line, v[1]))?;
+                let nanos = ::parse_token(line, v[2])?;
+                baseline.insert(day, phase, Duration::from_nanos(nanos));
+                Ok(())
+            })?;
+            Ok(baseline)
+        }
+    }
+
+    impl fmt::Display for Baseline {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            for (&(day, phase), time) in &self.times {
+                writeln!(f, "{} {} {}", day, phase, time.as_nanos())?;
             }
-            n
+            Ok(())
         }
+    }
 
-        pub fn get(&self, k: T) -> Option<HashSet<T>> {
-            self.edges.get(&k).cloned()
+    // A duration with three significant digits in a fitting unit
+    pub fn human(d: Duration) -> String {
+        let nanos = d.as_nanos() as f64;
+        let (value, unit) = if nanos < 1e3 {
+            (nanos, "ns")
+        } else if nanos < 1e6 {
+            (nanos / 1e3, "µs")
+        } else if nanos < 1e9 {
+            (nanos / 1e6, "ms")
+        } else {
+            (nanos / 1e9, "s")
+        };
+
+        if value < 10.0 {
+            format!("{:.2}{}", value, unit)
+        } else if value < 100.0 {
+            format!("{:.1}{}", value, unit)
+        } else {
+            format!("{:.0}{}", value, unit)
         }
     }
+
+    // The relative change from a baseline time, e.g. "+12.5%"
+    pub fn change(baseline: Duration, time: Duration) -> String {
+        let base = baseline.as_nanos() as f64;
+        if base == 0.0 {
+            return String::from("-");
+        }
+        format!("{:+.1}%", (time.as_nanos() as f64 - base) / base * 100.0)
+    }
 }
+
+#[macro_use]
+pub mod geom;
+pub mod asm;
+pub mod cfg;
+pub mod debugger;
+pub mod duet;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+pub mod hex;
+pub mod image;
+pub mod opt;
+pub mod par;
+pub mod spiral;
+pub mod tree;
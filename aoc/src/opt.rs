@@ -0,0 +1,291 @@
+// A peephole optimizer for duet programs. It looks for loops it knows the
+// outcome of and fuses each into a single operation a machine runs in place
+// of the whole loop:
+//
+// Mul: a loop adding constants to registers while counting a register down
+// to zero, or up from below, adds their products instead.
+//
+//   loop: sub a -3          # a += 3 * n, n = 0
+//         sub n 1
+//         jnz n loop
+//
+// Divides: the inner loop of day 23, setting f to 0 if d * e == b for some e
+// from e up to b.
+//
+//         set g d
+//         mul g e
+//         sub g b
+//         jnz g 2
+//         set f 0
+//         sub e -1
+//         set g e
+//         sub g b
+//         jnz g -8
+//
+// Composite: the loop of day 23 around Divides, which runs it for every d
+// from d up to b, restarting e every time. f ends up 0 if b is the product
+// of any two of them.
+//
+//         set e 2
+//         <Divides>
+//         sub d -1
+//         set g d
+//         sub g b
+//         jnz g -13
+//
+// The program itself is left alone. A fused loop only runs if the loop would
+// end and nothing overflows, otherwise the machine goes through the loop one
+// instruction at a time. Either way the registers end up the same, and so do
+// the counts of how often every instruction ran.
+
+use std::convert::TryFrom;
+
+use duet::Instruction;
+use duet::Opcode;
+use duet::Operand;
+use isqrt;
+
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub enum Kind {
+    Mul {
+        counter: usize,
+        step: i64,
+        jump: Opcode,
+        // Every register added to with what is added, negated for sub
+        adds: Vec<(usize, Operand, bool)>,
+    },
+    Divides{d: usize, e: usize, b: usize, f: usize, g: usize},
+    Composite{d: usize, e: usize, b: usize, f: usize, g: usize, start: Operand},
+}
+
+// A loop fused into one operation, replacing the instructions from start up
+// to, but not including, end
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Fused {
+    pub start: usize,
+    pub end: usize,
+    pub kind: Kind,
+}
+
+fn reg(o: Operand) -> Option<usize> {
+    match o {
+        Operand::Reg(r) => Some(r),
+        Operand::Imm(_) => None,
+    }
+}
+
+fn is(ins: &Instruction, op: Opcode, x: Operand, y: Option<Operand>) -> bool {
+    ins.op == op && ins.x == x && ins.y == y
+}
+
+// Adds the constant to the register, as add or sub
+fn step_of(ins: &Instruction) -> Option<(usize, i64)> {
+    match (ins.op, ins.x, ins.y) {
+        (Opcode::Add, Operand::Reg(r), Some(Operand::Imm(n))) => Some((r, n)),
+        (Opcode::Sub, Operand::Reg(r), Some(Operand::Imm(n))) => Some((r, n.checked_neg()?)),
+        _ => None,
+    }
+}
+
+fn distinct(regs: &[usize]) -> bool {
+    regs.iter().enumerate().all(|(i, r)| !regs[..i].contains(r))
+}
+
+fn find_mul(code: &[Instruction], start: usize) -> Option<Fused> {
+    // The closest jump back to start ends the loop
+    let end = (start + 1..code.len()).find(|&pc| {
+        code[pc].op.is_jump() && code[pc].y == Some(Operand::Imm(start as i64 - pc as i64))
+    })? + 1;
+    let jump = &code[end - 1];
+    let counter = reg(jump.x)?;
+
+    let mut step = None;
+    let mut adds = Vec::new();
+    for ins in &code[start..end - 1] {
+        let r = match ins.op {
+            Opcode::Add | Opcode::Sub => reg(ins.x)?,
+            _ => return None,
+        };
+        if r == counter {
+            match step_of(ins) {
+                Some((_, s)) if (s == 1 || s == -1) && step.is_none() => step = Some(s),
+                _ => return None,
+            }
+        } else {
+            adds.push((r, ins.y?, ins.op == Opcode::Sub));
+        }
+    }
+    // What is added can't change within the loop
+    let targets: Vec<_> = adds.iter().map(|a| a.0).chain(Some(counter)).collect();
+    if adds.iter().any(|a| reg(a.1).is_some_and(|r| targets.contains(&r))) {
+        return None;
+    }
+    Some(Fused{start, end, kind: Kind::Mul{counter, step: step?, jump: jump.op, adds}})
+}
+
+// The roles of the registers in the Divides loop at start
+fn divides(code: &[Instruction], start: usize) -> Option<(usize, usize, usize, usize, usize)> {
+    let c = code.get(start..start + 9)?;
+    let (g, d) = match (c[0].op, c[0].x, c[0].y) {
+        (Opcode::Set, Operand::Reg(g), Some(Operand::Reg(d))) => (g, d),
+        _ => return None,
+    };
+    let (e, b) = (reg(c[1].y?)?, reg(c[2].y?)?);
+    let f = reg(c[4].x)?;
+    let (r, g_, e_, f_) = (Operand::Reg, Operand::Reg(g), Operand::Reg(e), Operand::Reg(f));
+    let matches = is(&c[1], Opcode::Mul, g_, Some(e_))
+        && is(&c[2], Opcode::Sub, g_, Some(r(b)))
+        && is(&c[3], Opcode::Jnz, g_, Some(Operand::Imm(2)))
+        && is(&c[4], Opcode::Set, f_, Some(Operand::Imm(0)))
+        && step_of(&c[5]) == Some((e, 1))
+        && is(&c[6], Opcode::Set, g_, Some(e_))
+        && is(&c[7], Opcode::Sub, g_, Some(r(b)))
+        && is(&c[8], Opcode::Jnz, g_, Some(Operand::Imm(-8)));
+    if !matches || !distinct(&[d, e, b, f, g]) {
+        return None;
+    }
+    Some((d, e, b, f, g))
+}
+
+fn find_divides(code: &[Instruction], start: usize) -> Option<Fused> {
+    let (d, e, b, f, g) = divides(code, start)?;
+    Some(Fused{start, end: start + 9, kind: Kind::Divides{d, e, b, f, g}})
+}
+
+fn find_composite(code: &[Instruction], start: usize) -> Option<Fused> {
+    let c = code.get(start..start + 14)?;
+    let (d, e, b, f, g) = divides(code, start + 1)?;
+    let init = c[0].y?;
+    let matches = c[0].op == Opcode::Set && c[0].x == Operand::Reg(e)
+        && reg(init).is_none_or(|r| ![d, e, f, g].contains(&r))
+        && step_of(&c[10]) == Some((d, 1))
+        && is(&c[11], Opcode::Set, Operand::Reg(g), Some(Operand::Reg(d)))
+        && is(&c[12], Opcode::Sub, Operand::Reg(g), Some(Operand::Reg(b)))
+        && is(&c[13], Opcode::Jnz, Operand::Reg(g), Some(Operand::Imm(-13)));
+    if !matches {
+        return None;
+    }
+    Some(Fused{start, end: start + 14, kind: Kind::Composite{d, e, b, f, g, start: init}})
+}
+
+// Every loop that can be fused, by where it starts. Where loops start at the
+// same place the outer one is taken.
+pub fn find(code: &[Instruction]) -> Vec<Fused> {
+    (0..code.len())
+        .filter_map(|pc| {
+            find_composite(code, pc)
+                .or_else(|| find_divides(code, pc))
+                .or_else(|| find_mul(code, pc))
+        })
+        .collect()
+}
+
+// The number of pairs from [d0, b) x [e0, b) whose product is b, or None if
+// b has too many possible divisors to try
+fn products(b: i64, d0: i64, e0: i64) -> Option<usize> {
+    if b == 0 {
+        // Both factors are below zero then and so is neither of them
+        return Some(0);
+    }
+    let n = b.unsigned_abs();
+    if n > 1 << 40 {
+        return None;
+    }
+    let mut divisors = Vec::new();
+    for q in 1..=isqrt(n) {
+        if n.is_multiple_of(q) {
+            for &p in &[q, n / q] {
+                let p = p as i64;
+                divisors.push(p);
+                divisors.push(-p);
+            }
+        }
+    }
+    divisors.sort_unstable();
+    divisors.dedup();
+    Some(divisors.iter()
+        .filter(|&&d| d0 <= d && d < b)
+        .filter(|&&d| {
+            let e = b / d;
+            e0 <= e && e < b
+        })
+        .count())
+}
+
+// Whether x * y - b fits for all x and y within the bounds
+fn fits(xs: &[i64], ys: &[i64], b: i64) -> bool {
+    xs.iter().all(|&x| ys.iter().all(|&y| x.checked_mul(y).and_then(|p| p.checked_sub(b)).is_some()))
+}
+
+impl Fused {
+    // Runs the loop on the registers. Returns how often every instruction
+    // from start to end ran, or None without touching the registers if the
+    // loop has to run one instruction at a time.
+    pub fn run(&self, regs: &mut [i64]) -> Option<Vec<usize>> {
+        let value = |regs: &[i64], o: Operand| match o {
+            Operand::Imm(n) => n,
+            Operand::Reg(r) => regs[r],
+        };
+        match self.kind {
+            Kind::Mul{counter, step, jump, ref adds} => {
+                let c = regs[counter];
+                let n = match (jump, step) {
+                    (Opcode::Jnz, -1) if c > 0 => c,
+                    (Opcode::Jnz, 1) if c < 0 => c.checked_neg()?,
+                    (Opcode::Jgz, -1) => c.max(1),
+                    _ => return None,
+                };
+                let mut out = regs.to_vec();
+                for &(r, v, neg) in adds {
+                    let v = value(regs, v);
+                    let v = if neg { v.checked_neg()? } else { v };
+                    out[r] = out[r].checked_add(v.checked_mul(n)?)?;
+                }
+                out[counter] = c.checked_add(step * n)?;
+                regs.copy_from_slice(&out);
+                Some(vec![usize::try_from(n).ok()?; self.end - self.start])
+            }
+            Kind::Divides{d, e, b, f, g} => {
+                let (d, e0, bv) = (regs[d], regs[e], regs[b]);
+                if e0 >= bv || !fits(&[d], &[e0, bv], bv) {
+                    return None;
+                }
+                let n = usize::try_from(bv.checked_sub(e0)?).ok()?;
+                let hits = match (d, bv) {
+                    (0, 0) => n,
+                    (0, _) => 0,
+                    _ => match bv.checked_rem(d) {
+                        Some(0) => {
+                            let q = bv / d;
+                            (e0 <= q && q < bv) as usize
+                        }
+                        _ => 0,
+                    },
+                };
+                if hits > 0 {
+                    regs[f] = 0;
+                }
+                regs[e] = bv;
+                regs[g] = 0;
+                Some(vec![n, n, n, n, hits, n, n, n, n])
+            }
+            Kind::Composite{d, e, b, f, g, start} => {
+                let (d0, e0, bv) = (regs[d], value(regs, start), regs[b]);
+                if d0 >= bv || e0 >= bv || !fits(&[d0, bv - 1], &[e0, bv], bv) {
+                    return None;
+                }
+                let m = usize::try_from(bv.checked_sub(d0)?).ok()?;
+                let k = usize::try_from(bv.checked_sub(e0)?).ok()?;
+                let n = m.checked_mul(k)?;
+                let hits = products(bv, d0, e0)?;
+                if hits > 0 {
+                    regs[f] = 0;
+                }
+                regs[d] = bv;
+                regs[e] = bv;
+                regs[g] = 0;
+                Some(vec![m, n, n, n, n, hits, n, n, n, n, m, m, m, m])
+            }
+        }
+    }
+}
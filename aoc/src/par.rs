@@ -0,0 +1,56 @@
+// Spreading independent work over threads. Results come back in the order of
+// the inputs, whatever the number of threads.
+
+use std::env;
+use std::panic;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+pub const THREADS_VAR: &str = "AOC_THREADS";
+
+// The number of threads to use, from AOC_THREADS or else one per core
+pub fn threads() -> usize {
+    env::var(THREADS_VAR).ok()
+        .and_then(|n| n.parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+}
+
+// f applied to every input on up to the given number of threads. The threads
+// take the next input as they get done, so uneven work still spreads out.
+// With a single thread, or a single input, everything runs on the caller.
+pub fn map<T, U, F>(inputs: &[T], threads: usize, f: F) -> Vec<U>
+    where T: Sync,
+          U: Send,
+          F: Fn(&T) -> U + Sync,
+{
+    let threads = threads.min(inputs.len());
+    if threads <= 1 {
+        return inputs.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<U>> = inputs.iter().map(|_| None).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| scope.spawn(|| {
+                let mut done = Vec::new();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= inputs.len() {
+                        return done;
+                    }
+                    done.push((i, f(&inputs[i])));
+                }
+            }))
+            .collect();
+        for worker in workers {
+            let done = worker.join().unwrap_or_else(|e| panic::resume_unwind(e));
+            for (i, u) in done {
+                results[i] = Some(u);
+            }
+        }
+    });
+    results.into_iter().map(|u| u.unwrap()).collect()
+}
@@ -0,0 +1,94 @@
+// The square spiral of day 3. Square 1 is at the origin, square 2 right of it
+// and the spiral winds counterclockwise from there:
+//
+//   17  16  15  14  13
+//   18   5   4   3  12
+//   19   6   1   2  11
+//   20   7   8   9  10
+//   21  22  23---> ...
+//
+// Coordinates are (y, x) with y growing downwards, so square 4 is at (-1, 0).
+// Ring k holds the squares after (2k-1)^2 up to (2k+1)^2, which sits in its
+// bottom right corner.
+
+use std::convert::TryFrom;
+
+use geom::Vec2;
+use isqrt;
+
+// Where square n is, there are no squares below 1
+pub fn coord(n: i64) -> Option<Vec2<i64>> {
+    if n < 1 {
+        return None;
+    }
+    if n == 1 {
+        return Some(Vec2::default());
+    }
+    let k = (isqrt((n - 1) as u64) as i64 + 1) / 2;
+    let m = n - (2 * k - 1) * (2 * k - 1);
+    // Walk the ring with y pointing up: right side, top, left side, bottom
+    let (x, y) = match (m - 1) / (2 * k) {
+        0 => (k, m - k),
+        1 => (3 * k - m, k),
+        2 => (-k, 5 * k - m),
+        _ => (m - 7 * k, -k),
+    };
+    Some(Vec2::new(-y, x))
+}
+
+// The number of the square at p, if it fits into an i64
+pub fn index(p: Vec2<i64>) -> Option<i64> {
+    let (x, y) = (p.x as i128, -(p.y as i128));
+    let k = x.abs().max(y.abs());
+    if k == 0 {
+        return Some(1);
+    }
+    let m = if x == k && y > -k {
+        y + k
+    } else if y == k {
+        3 * k - x
+    } else if x == -k {
+        5 * k - y
+    } else {
+        7 * k + x
+    };
+    let inner = (2 * k - 1).checked_mul(2 * k - 1)?;
+    i64::try_from(inner + m).ok()
+}
+
+// Fills the spiral in order, writing into every square the sum of the
+// already filled squares around it. Square 1 starts out with 1. Stops when
+// the sums no longer fit into an u64.
+#[derive(Debug,Clone,Default)]
+pub struct NeighbourSums {
+    values: Vec<u64>,
+}
+
+pub fn neighbour_sums() -> NeighbourSums {
+    NeighbourSums::default()
+}
+
+impl Iterator for NeighbourSums {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let n = self.values.len() as i64 + 1;
+        let sum = if n == 1 {
+            1
+        } else {
+            let p = coord(n)?;
+            let mut sum = 0u64;
+            for dy in -1..2 {
+                for dx in -1..2 {
+                    match index(p + Vec2::new(dy, dx)) {
+                        Some(i) if i < n => sum = sum.checked_add(self.values[i as usize - 1])?,
+                        _ => (),
+                    }
+                }
+            }
+            sum
+        };
+        self.values.push(sum);
+        Some(sum)
+    }
+}
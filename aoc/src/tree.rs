@@ -0,0 +1,251 @@
+// A rooted tree of named, weighted nodes, like the program tower of day 7.
+// Nodes are referred to by their index, which follows the order they were
+// given in. The weight of every subtree is computed once when building.
+
+use std::cmp;
+use std::collections::HashMap;
+
+use Error;
+
+#[derive(Debug,Clone)]
+pub struct Tree {
+    names: Vec<String>,
+    weights: Vec<i64>,
+    sums: Vec<i64>,
+    children: Vec<Vec<usize>>,
+    parents: Vec<Option<usize>>,
+    index: HashMap<String, usize>,
+    root: usize,
+}
+
+// A node whose children carry different weights. expected is the subtree
+// weight most of the children agree on, the odd ones are those that don't.
+// With only two children, or no clear majority, there's no telling which
+// are odd and expected is None.
+#[derive(Debug,Clone,PartialEq,Eq)]
+pub struct Unbalanced {
+    pub node: usize,
+    pub expected: Option<i64>,
+    pub odd: Vec<usize>,
+}
+
+// Changing the weight of node to the fixed one balances the whole tree
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub struct Fix {
+    pub node: usize,
+    pub weight: i64,
+    pub fixed: i64,
+}
+
+impl Tree {
+    // Builds the tree from (name, weight, children) triples. Fails if a child
+    // isn't among the nodes, a node shows up twice or has two parents, or the
+    // nodes don't hang off exactly one root.
+    pub fn new<I, S>(nodes: I) -> Result<Tree, Error>
+        where I: IntoIterator<Item = (S, i64, Vec<S>)>,
+              S: AsRef<str>,
+    {
+        let mut names = Vec::new();
+        let mut weights = Vec::new();
+        let mut index = HashMap::new();
+        let mut edges = Vec::new();
+        for (name, weight, children) in nodes {
+            let name = name.as_ref();
+            if index.insert(name.to_string(), names.len()).is_some() {
+                return Err(Error::from(format!("Node \"{}\" is defined twice", name)));
+            }
+            names.push(name.to_string());
+            weights.push(weight);
+            edges.push(children);
+        }
+
+        let mut children = vec![Vec::new(); names.len()];
+        let mut parents = vec![None; names.len()];
+        for (parent, edges) in edges.iter().enumerate() {
+            for child in edges {
+                let child = child.as_ref();
+                let c = *index.get(child).ok_or_else(|| {
+                    format!("Child \"{}\" of \"{}\" does not exist", child, names[parent])
+                })?;
+                if let Some(other) = parents[c] {
+                    return Err(Error::from(format!("\"{}\" is a child of both \"{}\" and \"{}\"",
+                                                   child, names[other], names[parent])));
+                }
+                parents[c] = Some(parent);
+                children[parent].push(c);
+            }
+        }
+
+        let roots: Vec<_> = (0..names.len()).filter(|&n| parents[n].is_none()).collect();
+        let root = match roots.len() {
+            0 if names.is_empty() => return Err(Error::from("Empty tree")),
+            0 => return Err(Error::from(format!("Cycle through \"{}\"", names[0]))),
+            1 => roots[0],
+            _ => {
+                let roots: Vec<_> = roots.iter().map(|&r| names[r].as_str()).collect();
+                return Err(Error::from(format!("Multiple roots: {}", roots.join(", "))));
+            }
+        };
+
+        let mut tree = Tree{names, weights, sums: Vec::new(), children, parents, index, root};
+        tree.sums = tree.compute_sums()?;
+        Ok(tree)
+    }
+
+    // Subtree weights, adding up the children before their parents. Nodes
+    // that can't be reached from the root have to be sitting on a cycle.
+    fn compute_sums(&self) -> Result<Vec<i64>, Error> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack = vec![self.root];
+        while let Some(n) = stack.pop() {
+            order.push(n);
+            stack.extend(&self.children[n]);
+        }
+        if order.len() < self.len() {
+            let mut seen = vec![false; self.len()];
+            for &n in &order {
+                seen[n] = true;
+            }
+            let n = seen.iter().position(|&s| !s).unwrap();
+            return Err(Error::from(format!("Cycle through \"{}\"", self.names[n])));
+        }
+
+        let mut sums = self.weights.clone();
+        for &n in order.iter().rev() {
+            if let Some(p) = self.parents[n] {
+                sums[p] += sums[n];
+            }
+        }
+        Ok(sums)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.index.get(name).cloned()
+    }
+
+    pub fn name(&self, n: usize) -> &str {
+        &self.names[n]
+    }
+
+    pub fn weight(&self, n: usize) -> i64 {
+        self.weights[n]
+    }
+
+    // The weight of n and everything above it
+    pub fn sum(&self, n: usize) -> i64 {
+        self.sums[n]
+    }
+
+    pub fn children(&self, n: usize) -> &[usize] {
+        &self.children[n]
+    }
+
+    pub fn parent(&self, n: usize) -> Option<usize> {
+        self.parents[n]
+    }
+
+    pub fn depth(&self, mut n: usize) -> usize {
+        let mut depth = 0;
+        while let Some(p) = self.parents[n] {
+            n = p;
+            depth += 1;
+        }
+        depth
+    }
+
+    pub fn is_balanced(&self, n: usize) -> bool {
+        let children = &self.children[n];
+        children.iter().all(|&c| self.sums[c] == self.sums[children[0]])
+    }
+
+    fn check(&self, n: usize) -> Option<Unbalanced> {
+        if self.is_balanced(n) {
+            return None;
+        }
+        let mut counts: Vec<(i64, usize)> = Vec::new();
+        for &c in &self.children[n] {
+            let sum = self.sums[c];
+            match counts.iter_mut().find(|count| count.0 == sum) {
+                Some(count) => count.1 += 1,
+                None => counts.push((sum, 1)),
+            }
+        }
+        counts.sort_by_key(|&(_, n)| cmp::Reverse(n));
+        let expected = if counts[0].1 > counts[1].1 { Some(counts[0].0) } else { None };
+        let odd = match expected {
+            Some(e) => self.children[n].iter().cloned().filter(|&c| self.sums[c] != e).collect(),
+            None => Vec::new(),
+        };
+        Some(Unbalanced{node: n, expected, odd})
+    }
+
+    // Every node whose children don't weigh the same, from the root up
+    pub fn unbalanced(&self) -> Vec<Unbalanced> {
+        let mut all: Vec<_> = (0..self.len()).filter_map(|n| self.check(n)).collect();
+        all.sort_by_key(|u| (self.depth(u.node), u.node));
+        all
+    }
+
+    // The single weight that has to change to balance the tree, None if it is
+    // balanced already. Follows the odd children up from the lowest
+    // unbalanced node until reaching one whose own children agree, changing
+    // any weight below that would only move the problem.
+    pub fn fix(&self) -> Result<Option<Fix>, Error> {
+        let unbalanced = self.unbalanced();
+        let mut u = match unbalanced.first() {
+            Some(u) => u,
+            None => return Ok(None),
+        };
+        let mut visited = 1;
+        loop {
+            let (expected, odd) = match (u.expected, u.odd.as_slice()) {
+                (Some(e), &[o]) => (e, o),
+                _ => return Err(Error::Solve(format!(
+                    "Can't tell which child of \"{}\" is off", self.names[u.node]))),
+            };
+            match unbalanced.iter().find(|v| v.node == odd) {
+                Some(v) => {
+                    u = v;
+                    visited += 1;
+                }
+                None => {
+                    if visited < unbalanced.len() {
+                        return Err(Error::Solve(String::from("More than one weight is off")));
+                    }
+                    let delta = expected - self.sums[odd];
+                    if !self.balanced_with(odd, delta) {
+                        return Err(Error::Solve(String::from("No single weight balances the tree")));
+                    }
+                    let weight = self.weights[odd];
+                    return Ok(Some(Fix{node: odd, weight, fixed: weight + delta}));
+                }
+            }
+        }
+    }
+
+    // Whether everything below n would be balanced with delta added to the
+    // weight of n
+    fn balanced_with(&self, mut n: usize, delta: i64) -> bool {
+        while let Some(p) = self.parents[n] {
+            let sum = |c: usize| if c == n { self.sums[c] + delta } else { self.sums[c] };
+            let first = sum(self.children[p][0]);
+            if !self.children[p].iter().all(|&c| sum(c) == first) {
+                return false;
+            }
+            n = p;
+        }
+        true
+    }
+}
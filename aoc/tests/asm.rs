@@ -0,0 +1,87 @@
+extern crate aoc;
+
+use aoc::asm;
+use aoc::duet::InstructionSet;
+use aoc::duet::Opcode;
+use aoc::duet::Operand;
+use aoc::duet::State;
+use aoc::duet::Vm;
+
+const COUNTDOWN: &str = "
+# Counts down from N, adding up on the way
+const N = 10
+const START = N
+reg counter total
+
+        set counter START
+loop:   add total counter   # total += counter
+        sub counter 1
+        jnz counter loop
+end:
+";
+
+fn error(set: &InstructionSet, source: &str) -> String {
+    asm::assemble(set, source).unwrap_err().to_string()
+}
+
+#[test]
+fn lowering() {
+    let set = InstructionSet::full();
+    let p = asm::assemble(&set, COUNTDOWN).unwrap();
+    assert_eq!(p.set.register_count(), 28);
+    assert_eq!(p.set.register("total"), Some(27));
+    assert_eq!(p.code.len(), 4);
+    assert_eq!(p.code[0].y, Some(Operand::Imm(10)));
+    assert_eq!(p.code[3].op, Opcode::Jnz);
+    assert_eq!(p.code[3].y, Some(Operand::Imm(-2)));
+    assert_eq!(p.labels, vec![(String::from("loop"), 1), (String::from("end"), 4)]);
+    assert_eq!(p.labels_at(1).collect::<Vec<_>>(), vec!["loop"]);
+    assert_eq!(p.lines, vec![7, 8, 9, 10]);
+    assert_eq!(p.listing(), "set counter 10\nadd total counter\nsub counter 1\njnz counter -2\n");
+
+    let mut vm = Vm::new(&p.set, &p.code, ());
+    assert_eq!(vm.run().unwrap(), State::Halted);
+    assert_eq!(vm.reg(27), 55);
+}
+
+#[test]
+fn forward_labels() {
+    let set = InstructionSet::coprocessor();
+    let p = asm::assemble(&set, "jnz 1 skip\nset a 1\nskip: set b 2\n").unwrap();
+    assert_eq!(p.code[0].y, Some(Operand::Imm(2)));
+}
+
+#[test]
+fn plain() {
+    // Programs without any of the extras assemble to what the days parse
+    let set = InstructionSet::duet();
+    let source = "set a 1\nadd a 2\nmul a a\nmod a 5\nsnd a\nset a 0\nrcv a\njgz a -1\nset a 1\njgz a -2\n";
+    assert_eq!(asm::assemble(&set, source).unwrap().code, set.parse(source).unwrap());
+}
+
+#[test]
+fn errors() {
+    let set = InstructionSet::coprocessor();
+    assert_eq!(error(&set, "set a 1\n  jnz a nowhere\n"),
+               "Parse error at line 2, column 9: Unknown register, constant or label \"nowhere\"");
+    assert_eq!(error(&set, "x: set a 1\nset b x\n"),
+               "Parse error at line 2, column 7: Labels can only be jump offsets \"x\"");
+    assert_eq!(error(&set, "set a 1\nsnd a\n"),
+               "Parse error at line 2, column 1: Not in the instruction set \"snd\"");
+    assert_eq!(error(&set, "foo a 1\n"),
+               "Parse error at line 1, column 1: Invalid instruction \"foo\"");
+    assert_eq!(error(&set, "set a 1 # fine\nset a\n"),
+               "Parse error at line 2, column 1: set takes 2 operand(s) \"set a\"");
+    assert_eq!(error(&set, "const N = 1\nset N 2\n"),
+               "Parse error at line 2, column 5: Target isn't a register \"N\"");
+    assert_eq!(error(&set, "reg a\n"),
+               "Parse error at line 1, column 5: Defined twice \"a\"");
+    assert_eq!(error(&set, "l: set a 1\nl: set b 1\n"),
+               "Parse error at line 2, column 1: Defined twice \"l\"");
+    assert_eq!(error(&set, "const N = M\n"),
+               "Parse error at line 1, column 11: Expected a number or constant \"M\"");
+    assert_eq!(error(&set, "const N 1\n"),
+               "Parse error at line 1, column 1: Expected const <name> = <value> \"const N 1\"");
+    assert_eq!(error(&set, "reg 1x\n"),
+               "Parse error at line 1, column 5: Invalid name \"1x\"");
+}
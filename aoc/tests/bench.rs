@@ -0,0 +1,87 @@
+extern crate aoc;
+
+use std::time::Duration;
+
+use aoc::bench;
+use aoc::bench::Baseline;
+use aoc::bench::Phase;
+use aoc::bench::Stats;
+use aoc::Error;
+use aoc::Solution;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+struct Sum;
+
+impl Solution for Sum {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, Error> {
+        aoc::map_lines(input, |line| aoc::parse_token(line, line.trim()))
+    }
+
+    fn part1(input: &Vec<u32>) -> Result<u32, Error> {
+        Ok(input.iter().sum())
+    }
+
+    fn part2(_: &Vec<u32>) -> Result<u32, Error> {
+        Err(Error::Solve(String::from("No second part")))
+    }
+}
+
+#[test]
+fn stats() {
+    let stats = Stats::new(&[ms(4), ms(1), ms(3), ms(2)]).unwrap();
+    assert_eq!(stats.runs, 4);
+    assert_eq!(stats.min, ms(1));
+    assert_eq!(stats.max, ms(4));
+    assert_eq!(stats.median, Duration::from_micros(2500));
+    assert_eq!(stats.mean, Duration::from_micros(2500));
+    assert_eq!(stats.stddev, Duration::from_nanos(1_118_034));
+
+    assert_eq!(Stats::new(&[ms(5), ms(1), ms(3)]).unwrap().median, ms(3));
+    assert_eq!(Stats::new(&[]), None);
+}
+
+#[test]
+fn run() {
+    let timings = bench::run::<Sum>("1\n2\n3\n", 3).unwrap();
+    assert_eq!(timings.parse.runs, 3);
+    assert_eq!(timings.get(Phase::Part1).unwrap().runs, 3);
+    assert!(timings.part2.is_err());
+
+    assert!(bench::run::<Sum>("x", 3).is_err());
+}
+
+#[test]
+fn baseline() {
+    let mut baseline = Baseline::new();
+    baseline.insert(3, Phase::Part2, ms(2));
+    baseline.insert(3, Phase::Parse, Duration::from_nanos(1500));
+    baseline.insert(1, Phase::Part1, ms(10));
+
+    let text = baseline.to_string();
+    assert_eq!(text, "1 part1 10000000\n3 parse 1500\n3 part2 2000000\n");
+    assert_eq!(text.parse::<Baseline>().unwrap(), baseline);
+    assert_eq!(baseline.get(3, Phase::Part2), Some(ms(2)));
+    assert_eq!(baseline.get(3, Phase::Part1), None);
+
+    let e = "# comment\n1 part1 10\n1 part3 10\n".parse::<Baseline>().unwrap_err();
+    assert_eq!(e.to_string(), "Parse error at line 3, column 3: Invalid phase \"part3\"");
+}
+
+#[test]
+fn formatting() {
+    assert_eq!(bench::human(Duration::from_nanos(999)), "999ns");
+    assert_eq!(bench::human(Duration::from_nanos(1234)), "1.23µs");
+    assert_eq!(bench::human(Duration::from_micros(45678)), "45.7ms");
+    assert_eq!(bench::human(Duration::from_secs(2)), "2.00s");
+
+    assert_eq!(bench::change(ms(200), ms(250)), "+25.0%");
+    assert_eq!(bench::change(ms(200), ms(100)), "-50.0%");
+    assert_eq!(bench::change(ms(0), ms(100)), "-");
+}
@@ -0,0 +1,131 @@
+extern crate aoc;
+
+use aoc::asm;
+use aoc::cfg::Cfg;
+use aoc::cfg::Dest;
+use aoc::cfg::Edge;
+use aoc::duet::InstructionSet;
+
+// The prime counting program of day 23
+const PRIMES: &str = "set b 81\nset c b\njnz a 2\njnz 1 5\nmul b 100\nsub b -100000\nset c b\n\
+sub c -17000\nset f 1\nset d 2\nset e 2\nset g d\nmul g e\nsub g b\njnz g 2\nset f 0\nsub e -1\n\
+set g e\nsub g b\njnz g -8\nsub d -1\nset g d\nsub g b\njnz g -13\njnz f 2\nsub h -1\nset g b\n\
+sub g c\njnz g 2\njnz 1 3\nsub b -17\njnz 1 -23\n";
+
+fn cfg(set: &InstructionSet, source: &str) -> Cfg {
+    Cfg::new(&set.parse(source).unwrap())
+}
+
+#[test]
+fn blocks() {
+    let set = InstructionSet::coprocessor();
+    let g = cfg(&set, "set a 1\njnz a 2\nset b 1\nset c 1\n");
+    let b = g.blocks();
+    assert_eq!(b.len(), 3);
+    assert_eq!((b[0].start, b[0].end), (0, 2));
+    assert_eq!(b[0].succ, vec![Edge{to: Dest::Block(2), jump: true}, Edge{to: Dest::Block(1), jump: false}]);
+    assert_eq!(b[2].pred, vec![0, 1]);
+    assert_eq!(b[2].succ, vec![Edge{to: Dest::Exit, jump: false}]);
+    assert_eq!(g.block_at(3), Some(2));
+    assert_eq!(g.label(3), Some("L3"));
+    assert!(g.loops().is_empty());
+}
+
+#[test]
+fn nested_loops() {
+    let g = cfg(&InstructionSet::coprocessor(), PRIMES);
+    assert_eq!(g.blocks().len(), 14);
+    let headers: Vec<_> = g.loops().iter().map(|l| g.blocks()[l.header].start).collect();
+    assert_eq!(headers, vec![8, 10, 11]);
+    assert_eq!(g.loops()[2].blocks, vec![5, 6, 7]);
+    assert_eq!(g.loops()[0].latches, vec![13]);
+    assert_eq!(g.depth(g.block_at(15).unwrap()), 3);
+    assert_eq!(g.depth(g.block_at(25).unwrap()), 1);
+    assert_eq!(g.depth(g.block_at(29).unwrap()), 0);
+    assert!(g.is_back_edge(7, 5));
+    assert!(g.dominates(3, 13));
+    assert!(!g.dominates(1, 3));
+    assert_eq!(g.label(32), Some("end"));
+}
+
+#[test]
+fn listing() {
+    let set = InstructionSet::coprocessor();
+    let listing = cfg(&set, PRIMES).listing(&set);
+    assert!(listing.contains("# b5 [11, 15) <- b4 b7 -> b7 b6, loop 2 header, depth 3\n"), "{}", listing);
+    assert!(listing.contains("L11:    set g d"), "{}", listing);
+    assert!(listing.contains("jnz g L11      #  19 -> 11 if g != 0\n"), "{}", listing);
+    assert!(listing.contains("jnz 1 end      #  29 -> exit\n"), "{}", listing);
+
+    // The listing assembles back into the program
+    let program = asm::assemble(&set, &listing).unwrap();
+    assert_eq!(program.code, set.parse(PRIMES).unwrap());
+    assert_eq!(Cfg::from_program(&program).listing(&set), listing);
+}
+
+#[test]
+fn listing_round_trip() {
+    // Long labels and added registers survive the listing too
+    let set = InstructionSet::coprocessor();
+    let source = "reg counter\n        set counter 3\ncompute: sub counter 1\n        jnz counter compute\n";
+    let program = asm::assemble(&set, source).unwrap();
+    let listing = Cfg::from_program(&program).listing(&program.set);
+    assert!(listing.starts_with("reg counter\n"), "{}", listing);
+    assert!(listing.contains("compute: sub counter 1"), "{}", listing);
+
+    let again = asm::assemble(&set, &listing).unwrap();
+    assert_eq!(again.code, program.code);
+    assert_eq!(again.set, program.set);
+}
+
+#[test]
+fn generated_labels() {
+    // The jump to pc 1 can't be called L1 nor end, the source has both
+    let set = InstructionSet::coprocessor();
+    let source = "L1: jnz a end\n    sub a 1\nend: jnz a -1\n    jnz 1 L1\n    jnz 1 1\n";
+    let program = asm::assemble(&set, source).unwrap();
+    let g = Cfg::from_program(&program);
+    assert_eq!(g.label(0), Some("L1"));
+    assert_eq!(g.label(1), Some("L1_1"));
+    assert_eq!(g.label(2), Some("end"));
+    assert_eq!(g.label(5), Some("end_1"));
+
+    let listing = g.listing(&program.set);
+    assert_eq!(asm::assemble(&set, &listing).unwrap().code, program.code);
+}
+
+#[test]
+fn source_labels() {
+    let set = InstructionSet::coprocessor();
+    let p = asm::assemble(&set, "top: sub a 1\nunused: jnz a top\n").unwrap();
+    let g = Cfg::from_program(&p);
+    assert_eq!(g.label(0), Some("top"));
+    assert_eq!(g.label(1), None);
+}
+
+#[test]
+fn odd_flow() {
+    let set = InstructionSet::full();
+    // A jump by a register, one never taken and one to before the program
+    let g = cfg(&set, "jgz a b\njnz 0 5\njnz 1 -3\nset a 1\n");
+    let b = g.blocks();
+    assert_eq!(b[0].succ, vec![Edge{to: Dest::Unknown, jump: true}, Edge{to: Dest::Block(1), jump: false}]);
+    assert_eq!(b[1].succ, vec![Edge{to: Dest::Exit, jump: true}]);
+    assert!(!g.is_reachable(2));
+
+    // Entering a cycle in two places doesn't make a loop
+    let g = cfg(&set, "jgz a 3\nset b 1\njgz 1 2\nset b 2\njgz 1 -2\n");
+    assert!(g.loops().is_empty());
+}
+
+#[test]
+fn dot() {
+    let set = InstructionSet::coprocessor();
+    let dot = cfg(&set, "set a 3\nsub a 1\njnz a -1\n").dot(&set);
+    assert!(dot.starts_with("digraph cfg {\n"));
+    assert!(dot.contains("    b1 [label=\"b1\\lL1:\\l  1  sub a 1\\l  2  jnz a L1\\l\", peripheries=2];\n"), "{}", dot);
+    assert!(dot.contains("    entry -> b0;\n"));
+    assert!(dot.contains("    b1 -> b1 [label=\"taken\", style=dashed];\n"), "{}", dot);
+    assert!(dot.contains("    b1 -> exit [label=\"else\"];\n"), "{}", dot);
+    assert!(dot.ends_with("}\n"));
+}
@@ -0,0 +1,132 @@
+extern crate aoc;
+
+use aoc::asm;
+use aoc::debugger::Cmp;
+use aoc::debugger::Condition;
+use aoc::debugger::Debugger;
+use aoc::debugger::Event;
+use aoc::debugger::Point;
+use aoc::duet::InstructionSet;
+use aoc::duet::Mailbox;
+use aoc::duet::Operand;
+use aoc::duet::Sound;
+use aoc::duet::Vm;
+
+const COUNTDOWN: &str = "
+        set a 3
+loop:   sub a 1
+        sub b -2
+        jnz a loop
+";
+
+fn countdown() -> Debugger<()> {
+    let p = asm::assemble(&InstructionSet::coprocessor(), COUNTDOWN).unwrap();
+    let vm = Vm::new(&p.set, &p.code, ());
+    Debugger::new(p, vec![vm])
+}
+
+#[test]
+fn stepping() {
+    let mut d = countdown();
+    assert_eq!(d.command("step").unwrap(), "at 1 (loop): sub a 1");
+    assert_eq!(d.command("step 2").unwrap(), "at 3: jnz a loop");
+    // An empty line repeats the last command
+    assert_eq!(d.command("").unwrap(), "at 2: sub b -2");
+    assert_eq!(d.command("print a").unwrap(), "a = 1");
+    assert_eq!(d.command("continue").unwrap(), "No machine can go on: Halted");
+    assert_eq!(d.vms()[0].hits(), &[1, 3, 3, 3]);
+    assert_eq!(d.command("counts").unwrap(), "10 steps: set 1, sub 6, jnz 3");
+}
+
+#[test]
+fn breakpoints() {
+    let mut d = countdown();
+    assert_eq!(d.command("break loop if a == 1").unwrap(), "Breakpoint 1: break at 1 (loop) if a == 1");
+    assert_eq!(d.command("c").unwrap(), "Breakpoint 1, at 1 (loop): sub a 1");
+    assert_eq!(d.vms()[0].reg(0), 1);
+    assert_eq!(d.command("delete 1").unwrap(), "Deleted 1");
+
+    // A condition alone stops when it turns true
+    d.command("break if b >= 6").unwrap();
+    assert_eq!(d.command("c").unwrap(), "Breakpoint 2, at 3: jnz a loop");
+    assert_eq!(d.command("regs").unwrap(), "a=0 b=6 c=0 d=0 e=0 f=0 g=0 h=0");
+
+    let mut d = countdown();
+    d.add(Point::Break(Some(3), None));
+    assert_eq!(d.run().unwrap(), Event::Break{id: 1, vm: 0});
+    // Continuing leaves the breakpoint before checking it again
+    assert_eq!(d.run().unwrap(), Event::Break{id: 1, vm: 0});
+    assert_eq!(d.vms()[0].steps(), 6);
+
+    let c = Condition{reg: 0, cmp: Cmp::Lt, value: Operand::Reg(1)};
+    assert!(c.holds(&[1, 2]));
+    assert!(!c.holds(&[2, 2]));
+}
+
+#[test]
+fn break_before_running() {
+    let mut d = countdown();
+    assert_eq!(d.command("break 0").unwrap(), "Breakpoint 1: break at 0");
+    assert_eq!(d.command("c").unwrap(), "Breakpoint 1, at 0: set a 3");
+    assert_eq!(d.vms()[0].steps(), 0);
+    assert_eq!(d.command("c").unwrap(), "No machine can go on: Halted");
+
+    // A breakpoint on the pc a watchpoint stopped at still gets its turn
+    let mut d = countdown();
+    d.command("watch a").unwrap();
+    d.command("break loop").unwrap();
+    assert_eq!(d.run().unwrap(), Event::Watch{id: 1, vm: 0, reg: 0, old: 0, new: 3});
+    assert_eq!(d.run().unwrap(), Event::Break{id: 2, vm: 0});
+    assert_eq!(d.vms()[0].steps(), 1);
+}
+
+#[test]
+fn watchpoints() {
+    let mut d = countdown();
+    assert_eq!(d.command("watch a").unwrap(), "Watchpoint 1: watch a");
+    assert_eq!(d.command("c").unwrap(), "Watchpoint 1, a changed from 0 to 3\nat 1 (loop): sub a 1");
+    assert_eq!(d.run().unwrap(), Event::Watch{id: 1, vm: 0, reg: 0, old: 3, new: 2});
+    assert_eq!(d.command("info").unwrap(), "1: watch a");
+}
+
+#[test]
+fn sound() {
+    let set = InstructionSet::duet();
+    let p = asm::assemble(&set, "snd 7\nrcv 1\nset a 2\n").unwrap();
+    let vm = Vm::new(&p.set, &p.code, Sound::new());
+    let mut d = Debugger::new(p, vec![vm]);
+    assert_eq!(d.command("c").unwrap(), "Stopped by rcv, played 7, recovered 7\nat 2: set a 2");
+    assert_eq!(d.run().unwrap(), Event::Done);
+}
+
+#[test]
+fn messages() {
+    let set = InstructionSet::duet();
+    let p = asm::assemble(&set, "snd p\nrcv a\nadd a 10\nsnd a\nrcv b\n").unwrap();
+    let mut vms = vec![Vm::new(&p.set, &p.code, Mailbox::new()), Vm::new(&p.set, &p.code, Mailbox::new())];
+    vms[1].set_reg(p.set.register("p").unwrap(), 1);
+    let mut d = Debugger::new(p, vms);
+
+    assert_eq!(d.command("step").unwrap(), "vm 0 at 1: rcv a");
+    // The first machine waits, so the second one goes on
+    assert_eq!(d.command("step").unwrap(), "vm 1 at 1: rcv a");
+    assert_eq!(d.command("io").unwrap(), "vm 0: inbox [1], sent 1\nvm 1: inbox [0], sent 1");
+    assert_eq!(d.command("c").unwrap(), "No machine can go on: Halted, Halted");
+    assert_eq!(d.command("print b").unwrap(), "vm 0: b = 10\nvm 1: b = 11");
+}
+
+#[test]
+fn errors() {
+    let mut d = countdown();
+    assert_eq!(d.command("frobnicate").unwrap_err().to_string(),
+               "Parse error at column 1: Unknown command, try help \"frobnicate\"");
+    assert_eq!(d.command("break nowhere").unwrap_err().to_string(),
+               "Parse error at column 7: Unknown label \"nowhere\"");
+    assert_eq!(d.command("break 1 if a ~ 2").unwrap_err().to_string(),
+               "Parse error at column 14: Expected one of == != < <= > >= \"~\"");
+    assert_eq!(d.command("watch zz").unwrap_err().to_string(),
+               "Parse error at column 7: Unknown register \"zz\"");
+    assert_eq!(d.command("delete 5").unwrap_err().to_string(),
+               "Parse error at column 8: No such breakpoint or watchpoint \"5\"");
+    assert!(d.command("help").unwrap().starts_with("step [n]"));
+}
@@ -0,0 +1,99 @@
+extern crate aoc;
+
+use aoc::duet;
+use aoc::duet::Instruction;
+use aoc::duet::InstructionSet;
+use aoc::duet::Mailbox;
+use aoc::duet::Opcode;
+use aoc::duet::Operand;
+use aoc::duet::Sound;
+use aoc::duet::State;
+use aoc::duet::Vm;
+
+const SOUND: &str = "set a 1\nadd a 2\nmul a a\nmod a 5\nsnd a\nset a 0\nrcv a\njgz a -1\nset a 1\njgz a -2\n";
+const MESSAGES: &str = "snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d\n";
+
+#[test]
+fn parse() {
+    let set = InstructionSet::duet();
+    let prog = set.parse("set a 1\nsnd a\njgz 1 -2\n").unwrap();
+    assert_eq!(prog[0], Instruction{op: Opcode::Set, x: Operand::Reg(0), y: Some(Operand::Imm(1))});
+    assert_eq!(prog[1], Instruction{op: Opcode::Snd, x: Operand::Reg(0), y: None});
+    assert_eq!(set.format(&prog[2]), "jgz 1 -2");
+
+    assert!(set.parse_instruction("jnz a 2").is_err());
+    assert!(set.parse_instruction("nop a").is_err());
+    assert!(set.parse_instruction("set 1 2").is_err());
+    assert!(set.parse_instruction("snd a b").is_err());
+    assert!(InstructionSet::coprocessor().parse_instruction("set z 1").is_err());
+
+    let err = set.parse("set a 1\nadd A 1\n").unwrap_err().to_string();
+    assert!(err.contains("Invalid register name"), "{}", err);
+    assert!(err.contains("line 2"), "{}", err);
+}
+
+#[test]
+fn named_registers() {
+    let set = InstructionSet::new(vec![Opcode::Set, Opcode::Add]).named(vec!["acc", "x"]);
+    let prog = set.parse("set acc 4\nset x 3\nadd acc x\n").unwrap();
+    let mut vm = Vm::new(&set, &prog, ());
+    assert_eq!(vm.run().unwrap(), State::Halted);
+    assert_eq!(vm.registers(), &[7, 3]);
+}
+
+#[test]
+fn sound() {
+    let set = InstructionSet::duet();
+    let prog = set.parse(SOUND).unwrap();
+    let mut vm = Vm::new(&set, &prog, Sound::new());
+    assert_eq!(vm.run().unwrap(), State::Stopped);
+    assert_eq!(vm.io().recovered(), Some(4));
+    assert_eq!(vm.pc(), 7);
+    assert_eq!(vm.count(Opcode::Rcv), 2);
+    assert_eq!(vm.count(Opcode::Jgz), 3);
+}
+
+#[test]
+fn messages() {
+    let set = InstructionSet::duet();
+    let prog = set.parse(MESSAGES).unwrap();
+    let p = set.register("p").unwrap();
+    let mut vms = [Vm::new(&set, &prog, Mailbox::new()), Vm::new(&set, &prog, Mailbox::new())];
+    vms[1].set_reg(p, 1);
+    duet::run_pair(&mut vms).unwrap();
+
+    for vm in &vms {
+        assert_eq!(vm.state(), State::Blocked);
+        assert_eq!(vm.io().sent(), 3);
+        assert_eq!(vm.count(Opcode::Rcv), 3);
+        assert_eq!(vm.pc(), 6);
+    }
+    assert_eq!(&vms[0].registers()[..3], &[1, 2, 1]);
+    assert_eq!(&vms[1].registers()[..3], &[1, 2, 0]);
+}
+
+#[test]
+fn counters() {
+    let set = InstructionSet::coprocessor();
+    let prog = set.parse("set a 3\nmul b a\nsub a 1\njnz a -2\n").unwrap();
+    let mut vm = Vm::new(&set, &prog, ());
+    assert_eq!(vm.run().unwrap(), State::Halted);
+    assert_eq!(vm.counts(), vec![(Opcode::Set, 1), (Opcode::Sub, 3), (Opcode::Mul, 3), (Opcode::Jnz, 3)]);
+    assert_eq!(vm.steps(), 10);
+    assert_eq!(vm.hits(), &[1, 3, 3, 3]);
+    assert_eq!(vm.current(), None);
+}
+
+#[test]
+fn errors() {
+    let set = InstructionSet::full();
+    let prog = set.parse("mod a 0\n").unwrap();
+    let err = Vm::new(&set, &prog, ()).run().unwrap_err();
+    assert_eq!(err.to_string(), "Can't solve: Division by zero at pc 0");
+
+    // Without any io a rcv waits forever
+    let prog = set.parse("rcv a\n").unwrap();
+    let mut vm = Vm::new(&set, &prog, ());
+    assert_eq!(vm.run().unwrap(), State::Blocked);
+    assert_eq!(vm.steps(), 0);
+}
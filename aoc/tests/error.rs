@@ -0,0 +1,51 @@
+extern crate aoc;
+
+use aoc::Error;
+
+fn parse_pair(s: &str) -> Result<(u32, u32), Error> {
+    let v: Vec<_> = s.split(',').map(|t| t.trim()).collect();
+    if v.len() != 2 {
+        return Err(Error::token(s, s.trim(), "Expected a pair"));
+    }
+    Ok((aoc::parse_token(s, v[0])?, aoc::parse_token(s, v[1])?))
+}
+
+#[test]
+fn token_position() {
+    let line = "12, x4";
+    let e = parse_pair(line).unwrap_err();
+    assert_eq!(e, Error::Parse {
+        line: None,
+        column: Some(5),
+        token: String::from("x4"),
+        message: String::from("invalid digit found in string"),
+    });
+}
+
+#[test]
+fn line_numbers_count_blank_lines() {
+    let e = aoc::map_lines("1,2\n\n3,4\n5\n", parse_pair).unwrap_err();
+    assert_eq!(e.to_string(), "Parse error at line 4, column 1: Expected a pair \"5\"");
+}
+
+#[test]
+fn nested_tokens() {
+    let line = "a=<1,2> b=<3,y>";
+    let inner = &line[11..14];
+    let e = parse_pair(inner).unwrap_err().within(line, inner).at_line(7);
+    assert_eq!(e.to_string(), "Parse error at line 7, column 14: invalid digit found in string \"y\"");
+}
+
+#[test]
+fn messages_without_position() {
+    assert_eq!(Error::from("Empty input").to_string(), "Parse error: Empty input");
+    assert_eq!(Error::from("Empty input").at_line(3).to_string(), "Parse error at line 3: Empty input");
+    assert_eq!(Error::Solve(String::from("No answer")).at_line(3).to_string(), "Can't solve: No answer");
+}
+
+#[test]
+fn single_line() {
+    assert_eq!(aoc::map_line("\n 3,4 \n", parse_pair), Ok((3, 4)));
+    assert!(aoc::map_line("", parse_pair).is_err());
+    assert!(aoc::map_line("1,2\n3,4", parse_pair).is_err());
+}
@@ -0,0 +1,51 @@
+extern crate aoc;
+
+use aoc::geom::Dir4;
+use aoc::geom::Vec2;
+use aoc::geom::Vec3;
+
+#[test]
+fn vec2() {
+    let mut a = Vec2::new(1, -2);
+    let b = Vec2::new(3, 4);
+    assert_eq!(a + b, Vec2::new(4, 2));
+    assert_eq!(a - b, Vec2::new(-2, -6));
+    assert_eq!(-a, Vec2::new(-1, 2));
+    assert_eq!(a * 3, Vec2::new(3, -6));
+    a += b;
+    a -= Vec2::new(0, 1);
+    assert_eq!(a, Vec2::new(4, 1));
+    assert_eq!(Vec2::new(-3i64, 4).manhattan(), 7);
+    assert_eq!(Vec2::from((2, 5)), Vec2::new(2, 5));
+    assert_eq!(<(i32, i32)>::from(Vec2::new(2, 5)), (2, 5));
+}
+
+#[test]
+fn turns() {
+    let up = Vec2::new(-1, 0);
+    assert_eq!(up.rr(), Vec2::new(0, 1));
+    assert_eq!(up.rl(), Vec2::new(0, -1));
+    assert_eq!(up.rr().rr().rr().rr(), up);
+
+    assert_eq!(Dir4::Up.rr(), Dir4::Right);
+    assert_eq!(Dir4::Up.rl(), Dir4::Left);
+    assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+    for &d in &Dir4::all() {
+        assert_eq!(d.rr().vec(), d.vec().rr());
+        assert_eq!(d.reverse().vec(), -d.vec());
+    }
+}
+
+#[test]
+fn vec3() {
+    let v: Vec3<i64> = "<1,-2, 3>".parse().unwrap();
+    assert_eq!(v, Vec3::new(1, -2, 3));
+    assert_eq!(v.manhattan(), 6);
+    assert_eq!(v.to_string(), "<1,-2,3>");
+    assert_eq!(" 4,5,6 ".parse::<Vec3<i32>>().unwrap(), Vec3::new(4, 5, 6));
+    assert_eq!(v + v * 2 - Vec3::new(3, 0, 0), Vec3::new(0, -6, 9));
+
+    let e = "<1,2>".parse::<Vec3<i32>>().unwrap_err();
+    assert_eq!(e.to_string(), "Parse error at column 1: Expected a vector <x,y,z> \"<1,2>\"");
+    assert!("<1,x,2>".parse::<Vec3<i32>>().is_err());
+}
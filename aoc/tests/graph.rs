@@ -0,0 +1,94 @@
+extern crate aoc;
+
+use std::collections::HashSet;
+
+use aoc::graph::Graph;
+use aoc::graph::UnionFind;
+
+// The example pipes of day 12
+fn pipes() -> Graph<u32> {
+    let mut g = Graph::undirected();
+    for &(a, b) in &[(0, 2), (1, 1), (2, 3), (2, 4), (3, 4), (4, 6), (5, 6)] {
+        g.add_edge(a, b);
+    }
+    g
+}
+
+#[test]
+fn union_find() {
+    let mut u = UnionFind::new(5);
+    assert_eq!(u.sets(), 5);
+    assert!(u.union(0, 1));
+    assert!(u.union(3, 1));
+    assert!(!u.union(0, 3));
+    assert!(u.connected(0, 3));
+    assert!(!u.connected(0, 2));
+    assert_eq!(u.size(3), 3);
+    assert_eq!(u.sets(), 3);
+    let n = u.push();
+    assert_eq!((n, u.len(), u.sets()), (5, 6, 4));
+}
+
+#[test]
+fn edges() {
+    let mut g = pipes();
+    assert_eq!((g.len(), g.edge_count()), (7, 7));
+    assert!(!g.add_edge(2, 0));
+    assert!(g.has_edge(6, 4));
+    assert_eq!(g.neighbours(2).collect::<Vec<_>>(), vec![0, 3, 4]);
+    assert_eq!(g.degree(4), 3);
+    assert_eq!(g.degree(1), 1);
+    assert_eq!(g.degree(9), 0);
+    g.add_node(9);
+    assert!(g.contains(9));
+    assert_eq!(g.neighbours(9).count(), 0);
+
+    let mut d = Graph::directed();
+    d.add_edge('a', 'b');
+    d.add_edge('a', 'c');
+    d.add_edge('c', 'b');
+    assert!(d.is_directed());
+    assert!(!d.has_edge('b', 'a'));
+    assert_eq!((d.in_degree('b'), d.out_degree('b'), d.degree('a')), (2, 0, 2));
+}
+
+#[test]
+fn searches() {
+    let g = pipes();
+    let d = g.distances(0);
+    assert_eq!(d.len(), 6);
+    assert_eq!((d[&0], d[&4], d[&5]), (0, 2, 4));
+    assert_eq!(g.bfs(0).map(|(n, _)| n).collect::<Vec<_>>(), vec![0, 2, 3, 4, 6, 5]);
+    assert_eq!(g.dfs(0).collect::<Vec<_>>(), vec![0, 2, 3, 4, 6, 5]);
+    assert_eq!(g.dfs(6).collect::<Vec<_>>(), vec![6, 4, 2, 0, 3, 5]);
+    assert_eq!(g.reachable(1), [1].iter().cloned().collect::<HashSet<_>>());
+    assert_eq!(g.dfs(7).count(), 0);
+
+    assert_eq!(g.shortest_path(0, 5), Some(vec![0, 2, 4, 6, 5]));
+    assert_eq!(g.shortest_path(3, 3), Some(vec![3]));
+    assert_eq!(g.shortest_path(0, 1), None);
+
+    let mut d = Graph::directed();
+    d.add_edge(1, 2);
+    d.add_edge(2, 3);
+    assert_eq!(d.shortest_path(1, 3), Some(vec![1, 2, 3]));
+    assert_eq!(d.shortest_path(3, 1), None);
+}
+
+#[test]
+fn components() {
+    let mut g = pipes();
+    g.add_node(7);
+    let c = g.components();
+    assert_eq!(c.count(), 3);
+    assert_eq!(c.sizes(), &[6, 1, 1]);
+    assert_eq!((c.label(0), c.label(1), c.label(7), c.label(8)), (Some(0), Some(1), Some(2), None));
+    assert_eq!(c.members(0), g.reachable(5));
+    assert_eq!(c.labels().len(), 8);
+
+    // Direction doesn't matter for components
+    let mut d = Graph::directed();
+    d.add_edge(1, 2);
+    d.add_edge(3, 2);
+    assert_eq!(d.components().count(), 1);
+}
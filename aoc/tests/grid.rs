@@ -0,0 +1,132 @@
+extern crate aoc;
+
+use aoc::grid::Bounds;
+use aoc::grid::Grid;
+use aoc::grid::Sparse;
+
+fn grid(s: &str) -> Grid<bool> {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse() {
+    let g = grid("\n.#.\n..#\n###\n\n");
+    assert_eq!((g.height(), g.width()), (3, 3));
+    assert_eq!(g.row(2), Some(&[true, true, true][..]));
+    assert_eq!(g.column(0).unwrap().cloned().collect::<Vec<_>>(), vec![false, false, true]);
+    assert_eq!(g.to_string(), ".#.\n..#\n###\n");
+
+    let e = "..\n.x\n".parse::<Grid<bool>>().unwrap_err();
+    assert_eq!(e.to_string(), "Parse error at line 2, column 2: Invalid cell \"x\"");
+    let e = "..\n...\n".parse::<Grid<bool>>().unwrap_err();
+    assert_eq!(e.to_string(), "Parse error at line 2, column 1: Expected equal widths \"...\"");
+    assert!("\n\n".parse::<Grid<bool>>().is_err());
+}
+
+#[test]
+fn parse_rows() {
+    let s = "#./.#";
+    let g = Grid::parse_rows(s, s.split('/'), |c| if c == '#' { Some(1) } else { Some(0) }).unwrap();
+    assert_eq!(g, Grid::from_vec(2, 2, vec![1, 0, 0, 1]).unwrap());
+
+    let s = "#./.x";
+    let e = Grid::parse_rows(s, s.split('/'), |c| if c == '#' { Some(true) } else { None }).unwrap_err();
+    assert_eq!(e.to_string(), "Parse error at column 2: Invalid cell \".\"");
+}
+
+#[test]
+fn access() {
+    let mut g = Grid::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    assert_eq!(g.get((1, 2)), Some(&6));
+    assert_eq!(g.get((2, 0)), None);
+    assert_eq!(g.get((0, 3)), None);
+    assert_eq!(g.at(-1, 0), None);
+    assert_eq!(g.at(1, 0), Some(&4));
+    g[(0, 1)] = 7;
+    assert_eq!(g.row(0), Some(&[1, 7, 3][..]));
+    assert_eq!(g.positions().nth(4), Some(((1, 1), &5)));
+    assert!(Grid::from_vec(2, 2, vec![1]).is_none());
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds() {
+    let g = Grid::new(2, 2, 0);
+    assert_eq!(g[(0, 2)], 0);
+}
+
+#[test]
+fn neighbours() {
+    let g = Grid::new(3, 3, 0);
+    assert_eq!(g.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+    assert_eq!(g.neighbours4((1, 1)).count(), 4);
+    assert_eq!(g.neighbours8((1, 1)).count(), 8);
+    assert_eq!(g.neighbours8((2, 2)).collect::<Vec<_>>(), vec![(1, 1), (1, 2), (2, 1)]);
+}
+
+#[test]
+fn transform() {
+    let g = Grid::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+    assert_eq!(g.rotate(), Grid::from_vec(3, 2, vec![4, 1, 5, 2, 6, 3]).unwrap());
+    assert_eq!(g.rotate().rotate().rotate().rotate(), g);
+    assert_eq!(g.flip(), Grid::from_vec(2, 3, vec![3, 2, 1, 6, 5, 4]).unwrap());
+
+    let o = grid(".#.\n..#\n###").orientations();
+    assert_eq!(o.len(), 8);
+    assert!(o.contains(&grid("#..\n#.#\n##.")));
+}
+
+#[test]
+fn sub_grids() {
+    let mut g = Grid::from_vec(3, 3, (1..10).collect()).unwrap();
+    let sub = g.sub_grid((1, 1), 2, 2).unwrap();
+    assert_eq!(sub, Grid::from_vec(2, 2, vec![5, 6, 8, 9]).unwrap());
+    assert!(g.sub_grid((2, 2), 2, 2).is_none());
+
+    g.splice((0, 0), &sub).unwrap();
+    assert_eq!(g, Grid::from_vec(3, 3, vec![5, 6, 3, 8, 9, 6, 7, 8, 9]).unwrap());
+    assert!(g.splice((2, 0), &sub).is_none());
+
+    let s = sub.scale(2);
+    assert_eq!((s.height(), s.width()), (4, 4));
+    assert_eq!(s.row(1), Some(&[5, 5, 6, 6][..]));
+    assert_eq!(s.row(2), Some(&[8, 8, 9, 9][..]));
+
+    let p = sub.pad(1, 0);
+    assert_eq!((p.height(), p.width()), (4, 4));
+    assert_eq!(p.row(1), Some(&[0, 5, 6, 0][..]));
+}
+
+#[test]
+fn sparse() {
+    let mut s = Sparse::new('.');
+    assert_eq!(s.bounds(), None);
+    assert_eq!(*s.get((-1000, 1000)), '.');
+    assert_eq!(s.to_grid(), None);
+
+    s.set((-70, 3), '#');
+    *s.get_mut((2, -130)) = 'x';
+    assert_eq!(*s.get((-70, 3)), '#');
+    assert_eq!(*s.get((2, -130)), 'x');
+    assert_eq!(*s.get((2, -129)), '.');
+    assert_eq!(s.bounds(), Some(Bounds{min: (-70, -130), max: (2, 3)}));
+
+    let mut written: Vec<_> = s.iter().filter(|&(_, &c)| c != '.').collect();
+    written.sort();
+    assert_eq!(written, vec![((-70, 3), &'#'), ((2, -130), &'x')]);
+
+    let g = s.to_grid().unwrap();
+    assert_eq!((g.height(), g.width()), (73, 134));
+    assert_eq!(g[(0, 133)], '#');
+    assert_eq!(g[(72, 0)], 'x');
+}
+
+#[test]
+fn sparse_from_grid() {
+    let s = Sparse::from_grid(&grid("..#\n#..\n...\n"), (-1, -1), false);
+    assert_eq!(s.bounds(), Some(Bounds{min: (-1, -1), max: (1, 1)}));
+    assert!(*s.get((-1, 1)));
+    assert!(*s.get((0, -1)));
+    assert!(!*s.get((0, 0)));
+    assert_eq!(s.to_grid().unwrap().to_string(), "..#\n#..\n...\n");
+}
@@ -0,0 +1,107 @@
+extern crate aoc;
+
+use std::collections::HashMap;
+use std::hash::Hasher;
+
+use aoc::hash;
+use aoc::hash::BuildKnotHasher;
+use aoc::hash::Knot;
+use aoc::hash::KnotHasher;
+
+#[test]
+fn full_hash() {
+    for &(input, hash) in &hash::EXAMPLES {
+        assert_eq!(Knot::hash(input.as_bytes()).to_string(), hash);
+        assert_eq!(input.parse::<Knot>().unwrap().to_string(), hash);
+    }
+}
+
+#[test]
+fn streaming() {
+    let mut h = KnotHasher::new();
+    h.update(b"AoC").unwrap();
+    h.write(b" 20");
+    h.update(b"17").unwrap();
+    assert_eq!(h.dense().unwrap(), Knot::hash(b"AoC 2017"));
+    assert_eq!(h.sparse().unwrap().len(), 256);
+    assert_eq!(h.finish(), 0x33ef_eb34_ea91_902b);
+}
+
+#[test]
+fn single_round() {
+    // The example of day 10
+    let mut h = KnotHasher::single_round().size(5).unwrap();
+    for &l in &[3, 4, 1, 5] {
+        h.push_length(l).unwrap();
+    }
+    assert_eq!(h.sparse().unwrap(), vec![3, 4, 2, 1, 0]);
+    assert!(h.push_length(6).is_err());
+    assert_eq!(h.clone().rounds(0).sparse().unwrap(), vec![0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn configured() {
+    let mut h = KnotHasher::new().rounds(1).suffix(&[]).unwrap();
+    h.update(&[3, 4, 1, 5]).unwrap();
+    let mut single = KnotHasher::single_round();
+    single.update(&[3, 4, 1, 5]).unwrap();
+    assert_eq!(h, single);
+    assert_eq!(h.dense().unwrap().bytes().len(), 16);
+    let small = KnotHasher::new().suffix(&[17, 31]).unwrap().size(32).unwrap();
+    assert_eq!(small.dense().unwrap().bytes().len(), 2);
+}
+
+#[test]
+fn too_long() {
+    // The standard suffix doesn't fit on a small ring
+    assert_eq!(KnotHasher::new().size(5).unwrap_err().to_string(),
+               "Parse error: Length 17 is longer than the list");
+    assert!(KnotHasher::single_round().size(5).unwrap().suffix(&[6]).is_err());
+    for &size in &[0, 257] {
+        assert_eq!(KnotHasher::new().size(size).unwrap_err().to_string(),
+                   format!("Parse error: Ring sizes go from 1 to 256, not {}", size));
+    }
+
+    // Nor do the bytes, which are then all left out
+    let mut h = KnotHasher::single_round().size(5).unwrap();
+    h.update(&[3, 4]).unwrap();
+    assert!(h.update(&[1, 6]).is_err());
+    assert_eq!(h.sparse().unwrap(), vec![4, 3, 0, 1, 2]);
+    h.write(&[9]);
+    assert_eq!(h.sparse().unwrap_err().to_string(), "Parse error: Length 9 is longer than the list");
+    assert_eq!(h.finish(), 0);
+
+    // Growing the ring is fine, shrinking it below what it holds isn't
+    let mut h = KnotHasher::single_round().size(8).unwrap();
+    h.push_length(7).unwrap();
+    assert!(h.clone().size(6).is_err());
+    assert!(h.size(200).is_ok());
+}
+
+#[test]
+fn bits() {
+    let k = Knot::hash(b"flqrgnkx-0");
+    assert_eq!(k.len_bits(), 128);
+    assert_eq!(&k.to_grid()[..8], "##.#.#..");
+    assert!(k.bit(0) && !k.bit(2));
+    assert_eq!(&k.to_binary()[..8], "11010100");
+    assert_eq!(k.count_ones() as usize, k.bits().filter(|&b| b).count());
+}
+
+#[test]
+fn build_hasher() {
+    let mut m: HashMap<&str, u32, BuildKnotHasher> = HashMap::with_hasher(BuildKnotHasher);
+    m.insert("a", 1);
+    m.insert("b", 2);
+    assert_eq!(m["a"], 1);
+    assert_eq!(m.get("c"), None);
+}
+
+#[test]
+fn checksums() {
+    assert_eq!(hash::crc32(b""), 0);
+    assert_eq!(hash::crc32(b"123456789"), 0xcbf4_3926);
+    assert_eq!(hash::crc32(b"IEND"), 0xae42_6082);
+    assert_eq!(hash::adler32(b""), 1);
+    assert_eq!(hash::adler32(b"Wikipedia"), 0x11e6_0398);
+}
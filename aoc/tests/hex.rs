@@ -0,0 +1,113 @@
+extern crate aoc;
+
+use aoc::hex;
+use aoc::hex::Axial;
+use aoc::hex::Hex;
+use aoc::hex::HexDir;
+use aoc::hex::Layout;
+use aoc::hex::Offset;
+
+fn walk(s: &str) -> Hex<i32> {
+    hex::parse_dirs(s).unwrap().into_iter().fold(Hex::default(), |p, d| p.step(d))
+}
+
+#[test]
+fn directions() {
+    let p = HexDir::all().iter().fold(Hex::<i32>::default(), |p, d| p + d.hex());
+    assert_eq!(p, Hex::default());
+    for &d in &HexDir::all() {
+        let h: Hex<i32> = d.hex();
+        assert_eq!(h.distance(), 1);
+        assert_eq!(d.rr().hex(), h.rr());
+        assert_eq!(d.rl().hex(), h.rl());
+        assert_eq!(d.reverse().hex(), -h);
+    }
+
+    let p = HexDir::NE.hex::<i32>() * 3 + HexDir::S.hex();
+    assert_eq!(p.distance(), 3);
+    assert_eq!(p.distance_to(HexDir::NW.hex()), 4);
+}
+
+#[test]
+fn parse() {
+    assert_eq!(hex::parse_dirs("ne,ne,s,s\n").unwrap(),
+               vec![HexDir::NE, HexDir::NE, HexDir::S, HexDir::S]);
+    let e = hex::parse_dirs("n,up").unwrap_err();
+    assert_eq!(e.to_string(), "Parse error at column 3: Unexpected direction \"up\"");
+    assert_eq!(hex::format_dirs(&[HexDir::SW, HexDir::N]), "sw,n");
+}
+
+#[test]
+fn coordinates() {
+    let p = walk("ne,ne,s");
+    assert_eq!(p.to_axial(), Axial{q: 2, r: -1});
+    assert_eq!(Hex::from(Axial{q: 2, r: -1}), p);
+
+    assert_eq!(p.to_offset(Layout::OddQ), Offset{col: 2, row: 0});
+    assert_eq!(p.to_offset(Layout::EvenQ), Offset{col: 2, row: 0});
+    let q = walk("sw,n");
+    assert_eq!(q.to_offset(Layout::OddQ), Offset{col: -1, row: -1});
+    assert_eq!(q.to_offset(Layout::EvenQ), Offset{col: -1, row: 0});
+
+    for h in Hex::<i32>::default().spiral(3) {
+        for &layout in &[Layout::OddQ, Layout::EvenQ] {
+            assert_eq!(Hex::from_offset(h.to_offset(layout), layout), h);
+        }
+        assert_eq!(Hex::from(Axial::from(h)), h);
+    }
+}
+
+#[test]
+fn rings() {
+    let c = walk("se,se");
+    assert_eq!(c.neighbours().collect::<Vec<_>>(),
+               HexDir::all().iter().map(|&d| c.step(d)).collect::<Vec<_>>());
+    assert_eq!(c.ring(0).collect::<Vec<_>>(), vec![c]);
+
+    let ring: Vec<_> = c.ring(2).collect();
+    assert_eq!(ring.len(), 12);
+    assert_eq!(ring[0], c + HexDir::N.hex() * 2);
+    assert!(ring.iter().all(|h| h.distance_to(c) == 2));
+    assert!(ring.windows(2).all(|w| w[0].distance_to(w[1]) == 1));
+
+    let spiral: Vec<_> = c.spiral(2).collect();
+    assert_eq!(spiral.len(), 19);
+    assert_eq!(spiral[0], c);
+    assert!(spiral.iter().all(|h| h.distance_to(c) <= 2));
+}
+
+#[test]
+fn lines() {
+    let a = walk("sw,sw");
+    let b = walk("n,n,ne,ne,ne");
+    let line: Vec<_> = a.line(b).collect();
+    assert_eq!(line.len(), a.distance_to(b) as usize + 1);
+    assert_eq!((line[0], line[line.len() - 1]), (a, b));
+    assert!(line.windows(2).all(|w| w[0].distance_to(w[1]) == 1));
+    assert_eq!(a.line(a).collect::<Vec<_>>(), vec![a]);
+}
+
+#[test]
+fn paths() {
+    assert_eq!(hex::format_dirs(&walk("ne,ne,ne").path_home()), "sw,sw,sw");
+    assert_eq!(hex::format_dirs(&walk("ne,ne,sw,sw").path_home()), "");
+    assert_eq!(hex::format_dirs(&walk("ne,ne,s,s").path_home()), "nw,nw");
+    assert_eq!(hex::format_dirs(&walk("se,sw,se,sw,sw").path_home()), "n,n,ne");
+    assert_eq!(hex::format_dirs(&Hex::default().path_to(walk("n,nw,n,s,nw"))), "n,nw,nw");
+
+    for h in Hex::<i32>::default().spiral(4) {
+        let path = h.path_home();
+        assert_eq!(path.len() as i32, h.distance());
+        assert_eq!(path.iter().fold(h, |p, &d| p.step(d)), Hex::default());
+    }
+}
+
+#[test]
+fn wide() {
+    let far = HexDir::NE.hex::<i64>() * 3_000_000_000 + HexDir::S.hex();
+    assert_eq!(far.distance(), 3_000_000_000);
+    assert_eq!(far.to_axial(), Axial{q: 3_000_000_000, r: -2_999_999_999});
+    assert_eq!(Hex::from_offset(far.to_offset(Layout::OddQ), Layout::OddQ), far);
+    assert_eq!(far.path_to(far + HexDir::SW.hex() * 2), vec![HexDir::SW, HexDir::SW]);
+    assert_eq!(HexDir::N.hex::<i8>().ring(1).count(), 6);
+}
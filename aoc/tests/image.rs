@@ -0,0 +1,73 @@
+extern crate aoc;
+
+use aoc::grid::Grid;
+use aoc::hash;
+use aoc::image;
+
+fn be32(b: &[u8]) -> u32 {
+    b.iter().fold(0, |n, &b| n << 8 | u32::from(b))
+}
+
+#[test]
+fn pbm() {
+    let g: Grid<bool> = "#........#\n.#.......#\n".parse().unwrap();
+    let mut expected = b"P4\n10 2\n".to_vec();
+    expected.extend_from_slice(&[0x80, 0x40, 0x40, 0x40]);
+    assert_eq!(image::pbm(&g), expected);
+}
+
+#[test]
+fn pgm() {
+    let g = Grid::from_vec(2, 2, vec![0, 64, 128, 255]).unwrap();
+    assert_eq!(image::pgm(&g), b"P5\n2 2\n255\n\x00\x40\x80\xff".to_vec());
+}
+
+#[test]
+fn png() {
+    let mut g = Grid::new(2, 3, image::WHITE);
+    g[(1, 2)] = image::colour(5);
+    let png = image::png(&g);
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+    // Walk the chunks and check their checksums
+    let mut chunks = Vec::new();
+    let mut rest = &png[8..];
+    while !rest.is_empty() {
+        let len = be32(&rest[..4]) as usize;
+        let (kind, data) = (&rest[4..8], &rest[8..8 + len]);
+        assert_eq!(be32(&rest[8 + len..12 + len]), hash::crc32(&rest[4..8 + len]));
+        chunks.push((kind.to_vec(), data.to_vec()));
+        rest = &rest[12 + len..];
+    }
+    let kinds: Vec<_> = chunks.iter().map(|c| c.0.as_slice()).collect();
+    assert_eq!(kinds, vec![&b"IHDR"[..], &b"IDAT"[..], &b"IEND"[..]]);
+    assert_eq!(chunks[0].1, vec![0, 0, 0, 3, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+
+    // A single stored block holding both filtered rows
+    let idat = &chunks[1].1;
+    let raw = &idat[7..idat.len() - 4];
+    assert_eq!(&idat[..7], &[0x78, 0x01, 1, 20, 0, !20, 0xff]);
+    assert_eq!(raw.len(), 2 * (1 + 3 * 3));
+    assert_eq!(&raw[..4], &[0, 255, 255, 255]);
+    assert_eq!(&raw[17..], &image::colour(5)[..]);
+    assert_eq!(be32(&idat[idat.len() - 4..]), hash::adler32(raw));
+}
+
+#[test]
+fn large_png() {
+    // More than one stored block is needed past 65535 bytes
+    let g = Grid::new(200, 200, image::BLACK);
+    let png = image::png(&g);
+    let raw = 200 * (1 + 200 * 3);
+    assert_eq!(png.len(), 8 + 25 + 12 + 2 + 2 * 5 + raw + 4 + 12);
+}
+
+#[test]
+fn colours() {
+    let colours: Vec<_> = (0..20).map(image::colour).collect();
+    for (i, c) in colours.iter().enumerate() {
+        assert!(*c != image::WHITE && *c != image::BLACK);
+        assert!(colours[i + 1..].iter().all(|d| d != c));
+    }
+    assert!((0..1000).all(|n| image::grey(n) < 255));
+}
@@ -0,0 +1,84 @@
+extern crate aoc;
+
+use std::io;
+use std::io::BufReader;
+use std::io::Read;
+
+use aoc::input;
+
+// "0 2 7 0\n\n3 1\n", gzip compressed
+const GZIPPED: &[u8] = &[
+    0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x33, 0x50, 0x30, 0x52, 0x30, 0x57,
+    0x30, 0xe0, 0xe2, 0x32, 0x56, 0x30, 0xe4, 0x02, 0x00, 0x83, 0x2f, 0x52, 0x23, 0x0d, 0x00, 0x00,
+    0x00,
+];
+
+// Yields its data and then fails
+struct Broken<'a>(&'a [u8]);
+
+impl<'a> Read for Broken<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() {
+            return Err(io::Error::other("disk on fire"));
+        }
+        self.0.read(buf)
+    }
+}
+
+#[test]
+fn lines_skip_blanks() {
+    let lines: Vec<_> = input::lines("a\n\n  \nb c\n\n".as_bytes()).map(Result::unwrap).collect();
+    assert_eq!(lines, vec!["a", "b c"]);
+}
+
+#[test]
+fn lines_surface_errors() {
+    let mut lines = input::lines(BufReader::new(Broken(b"a\n\nb\n")));
+    assert_eq!(lines.next().unwrap().unwrap(), "a");
+    assert_eq!(lines.next().unwrap().unwrap(), "b");
+    assert_eq!(lines.next().unwrap().unwrap_err().to_string(), "disk on fire");
+}
+
+#[test]
+fn all_surfaces_errors() {
+    assert_eq!(input::all("0 2 7 0".as_bytes()).unwrap(), "0 2 7 0");
+    let e: aoc::Error = input::all(Broken(b"0 2")).unwrap_err().into();
+    assert_eq!(e.to_string(), "I/O error: disk on fire");
+}
+
+#[test]
+fn parse_reports_io_errors() {
+    let sum = |s: &str| aoc::map_lines(s, |l| Ok(l.parse::<i64>()?)).map(|v| v.iter().sum::<i64>());
+    assert_eq!(input::parse("1\n2\n".as_bytes(), sum).unwrap(), 3);
+    assert_eq!(input::parse(Broken(b"1\n"), sum).unwrap_err(),
+               aoc::Error::Io(String::from("disk on fire")));
+    assert_eq!(input::parse(&b"1\n\xff\n"[..], sum).unwrap_err().to_string(),
+               "I/O error: stream did not contain valid UTF-8");
+    assert!(input::parse("1\nx\n".as_bytes(), sum).unwrap_err().to_string().starts_with("Parse error at line 2"));
+}
+
+#[test]
+fn missing_file() {
+    assert!(input::open("does/not/exist.txt").is_err());
+}
+
+#[test]
+fn plain_passes_through() {
+    let lines: Vec<_> = input::lines(input::decompress(&b"1\n2\n"[..]).unwrap())
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(lines, vec!["1", "2"]);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip() {
+    let lines: Vec<_> = input::lines(input::decompress(GZIPPED).unwrap()).map(Result::unwrap).collect();
+    assert_eq!(lines, vec!["0 2 7 0", "3 1"]);
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn gzip_disabled() {
+    assert!(input::decompress(GZIPPED).is_err());
+}
@@ -0,0 +1,50 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::log;
+use aoc::log::Level;
+
+#[test]
+fn levels() {
+    assert!(Level::Error < Level::Warn && Level::Debug < Level::Trace);
+    assert_eq!("debug".parse::<Level>(), Ok(Level::Debug));
+    assert_eq!(" TRACE ".parse::<Level>(), Ok(Level::Trace));
+    assert!("loud".parse::<Level>().is_err());
+    assert_eq!(Level::Info.to_string(), "info");
+}
+
+#[test]
+fn flags() {
+    assert_eq!(log::flag("-v"), Some(1));
+    assert_eq!(log::flag("-vvv"), Some(3));
+    assert_eq!(log::flag("--verbose"), Some(1));
+    assert_eq!(log::flag("--trace"), Some(3));
+    assert_eq!(log::flag("-"), None);
+    assert_eq!(log::flag("-x"), None);
+    assert_eq!(log::flag("input.txt"), None);
+}
+
+// The only test touching the global level
+#[test]
+fn verbosity() {
+    let mut evaluated = 0;
+    let mut count = || {
+        evaluated += 1;
+        evaluated
+    };
+
+    assert_eq!(log::level(), Level::Warn);
+    assert!(!log::enabled(Level::Info));
+    debug!("not shown {}", count());
+
+    log::more(2);
+    assert_eq!(log::level(), Level::Debug);
+    debug!("shown {}", count());
+    trace!("not shown {}", count());
+
+    log::more(5);
+    assert_eq!(log::level(), Level::Trace);
+
+    log::set_level(Level::Warn);
+    assert_eq!(evaluated, 1);
+}
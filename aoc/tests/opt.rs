@@ -0,0 +1,136 @@
+extern crate aoc;
+
+use aoc::duet::InstructionSet;
+use aoc::duet::Opcode;
+use aoc::duet::Operand;
+use aoc::duet::Vm;
+use aoc::opt;
+use aoc::opt::Kind;
+
+// The loop of day 23 testing whether d divides b
+const DIVIDES: &str = "set g d\nmul g e\nsub g b\njnz g 2\nset f 0\nsub e -1\nset g e\nsub g b\njnz g -8\n";
+
+// And the one around it, testing whether b is composite
+fn composite(e: i64) -> String {
+    format!("set f 1\nset e {}\n{}sub d -1\nset g d\nsub g b\njnz g -13\n", e, DIVIDES)
+}
+
+// Runs the program from the registers with and without fusing its loops,
+// which has to end the same and count the same
+fn same(set: &InstructionSet, source: &str, regs: &[(usize, i64)], fused: usize) -> Vec<i64> {
+    let prog = set.parse(source).unwrap();
+    let mut plain = Vm::new(set, &prog, ());
+    let mut fast = Vm::new(set, &prog, ());
+    for &(r, v) in regs {
+        plain.set_reg(r, v);
+        fast.set_reg(r, v);
+    }
+    assert_eq!(fast.optimize(), fused);
+    plain.run().unwrap();
+    fast.run().unwrap();
+    assert_eq!(fast.registers(), plain.registers(), "{:?}", regs);
+    assert_eq!(fast.counts(), plain.counts(), "{:?}", regs);
+    assert_eq!(fast.hits(), plain.hits(), "{:?}", regs);
+    assert_eq!(fast.steps(), plain.steps(), "{:?}", regs);
+    plain.registers().to_vec()
+}
+
+#[test]
+fn find() {
+    let set = InstructionSet::coprocessor();
+    let found = opt::find(&set.parse(&composite(2)).unwrap());
+    let starts: Vec<_> = found.iter().map(|f| (f.start, f.end)).collect();
+    assert_eq!(starts, vec![(1, 15), (2, 11)]);
+    assert_eq!(found[0].kind, Kind::Composite{d: 3, e: 4, b: 1, f: 5, g: 6, start: Operand::Imm(2)});
+
+    let set = InstructionSet::full();
+    let found = opt::find(&set.parse("sub a -3\nsub b c\nsub n 1\njnz n -3\n").unwrap());
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].kind, Kind::Mul{
+        counter: 13,
+        step: -1,
+        jump: Opcode::Jnz,
+        adds: vec![(0, Operand::Imm(-3), true), (1, Operand::Reg(2), true)],
+    });
+
+    // Loops changing what they add, or counting by more than one, are left
+    for source in &["sub a b\nsub b 1\njnz b -2\n", "sub a 1\nsub n 2\njnz n -2\n",
+                    &DIVIDES.replace("set g e\nsub g b", "set g e\nsub g d")] {
+        assert_eq!(opt::find(&set.parse(source).unwrap()), vec![], "{}", source);
+    }
+}
+
+#[test]
+fn mul() {
+    let set = InstructionSet::full();
+    let mut vm = Vm::new(&set, &set.parse("set n 1000000000\nadd a 3\nsub b c\nadd n -1\njgz n -3\n").unwrap(), ());
+    vm.set_reg(2, 2);
+    assert_eq!(vm.optimize(), 1);
+    vm.run().unwrap();
+    assert_eq!(&vm.registers()[..3], &[3_000_000_000, -2_000_000_000, 2]);
+    assert_eq!(vm.steps(), 4_000_000_001);
+
+    // c and n are registers 2 and 13
+    for &n in &[1, 2, 17] {
+        same(&set, "sub a -3\nsub b c\nsub n 1\njnz n -3\n", &[(2, 5), (13, n)], 1);
+        same(&set, "sub a -3\nsub b c\nsub n -1\njnz n -3\n", &[(2, 5), (13, -n)], 1);
+    }
+    for &n in &[-4, 0, 1, 9] {
+        same(&set, "set f 1\nsub a -3\nsub b c\nsub n 1\njgz n -3\n", &[(2, 5), (13, n)], 1);
+        // Counting up only goes around once, which isn't fused
+        same(&set, "set f 1\nsub a -3\nsub n -1\njgz n -2\n", &[(13, -n.abs() - 1)], 1);
+    }
+}
+
+#[test]
+fn composite_loop() {
+    let set = InstructionSet::coprocessor();
+    // b, d and f are registers 1, 3 and 5
+    for b in 2..40 {
+        for &(d, e) in &[(2, 2), (1, 1), (5, 3), (-3, -2), (0, 0), (b - 1, b - 1)] {
+            if d >= b || e >= b {
+                continue;
+            }
+            let regs = same(&set, &composite(e), &[(1, b), (3, d)], 2);
+            let composite = (d..b).any(|x| (e..b).any(|y| x * y == b));
+            assert_eq!(regs[5] == 0, composite, "b {} d {} e {}", b, d, e);
+        }
+    }
+    same(&set, &composite(-3), &[(1, 0), (3, -5)], 2);
+    same(&set, &composite(-8), &[(1, -6), (3, -9)], 2);
+}
+
+#[test]
+fn divides_loop() {
+    let set = InstructionSet::coprocessor();
+    // b, d, e and f are registers 1, 3, 4 and 5
+    for b in -6..30 {
+        for d in -3..8 {
+            for &e in &[b - 9, b - 1, 0, 1, 2] {
+                if e < b {
+                    let regs = same(&set, DIVIDES, &[(1, b), (3, d), (4, e), (5, 1)], 1);
+                    assert_eq!(regs[5] == 0, (e..b).any(|y| d * y == b), "b {} d {} e {}", b, d, e);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn fallback() {
+    let set = InstructionSet::coprocessor();
+    let found = opt::find(&set.parse(&composite(2)).unwrap());
+    // The loops would run until b wraps around
+    let mut regs = vec![0; 8];
+    regs[1] = 5;
+    regs[3] = 7;
+    assert_eq!(found[0].run(&mut regs), None);
+    regs[3] = 2;
+    regs[4] = 9;
+    assert_eq!(found[1].run(&mut regs), None);
+    // Or until the products overflow
+    regs[1] = 1 << 62;
+    regs[4] = 0;
+    assert_eq!(found[1].run(&mut regs), None);
+    assert_eq!(regs, vec![0, 1 << 62, 0, 2, 0, 0, 0, 0]);
+}
@@ -0,0 +1,48 @@
+extern crate aoc;
+
+use std::thread;
+use std::time::Duration;
+
+use aoc::hash::Knot;
+use aoc::par;
+
+#[test]
+fn ordered() {
+    let inputs: Vec<u64> = (0..100).collect();
+    let expected: Vec<u64> = inputs.iter().map(|n| n * n).collect();
+    for threads in 0..9 {
+        assert_eq!(par::map(&inputs, threads, |n| n * n), expected);
+    }
+    assert_eq!(par::map(&[] as &[u64], 4, |n| n * n), Vec::<u64>::new());
+}
+
+#[test]
+fn uneven_work() {
+    // Early inputs take longest, so they finish last
+    let inputs: Vec<u64> = (0..8).collect();
+    let out = par::map(&inputs, 4, |&n| {
+        thread::sleep(Duration::from_millis(8 - n));
+        n
+    });
+    assert_eq!(out, inputs);
+}
+
+#[test]
+fn knot_rows() {
+    let rows: Vec<String> = (0..128).map(|i| format!("flqrgnkx-{}", i)).collect();
+    let sequential = par::map(&rows, 1, |r| Knot::hash(r.as_bytes()));
+    let parallel = par::map(&rows, 5, |r| Knot::hash(r.as_bytes()));
+    assert_eq!(sequential, parallel);
+    assert_eq!(parallel.iter().map(|k| k.count_ones()).sum::<u32>(), 8108);
+}
+
+#[test]
+#[should_panic]
+fn panics_propagate() {
+    par::map(&[1, 2, 3, 4], 2, |&n| if n == 3 { panic!("three") } else { n });
+}
+
+#[test]
+fn default_threads() {
+    assert!(par::threads() >= 1);
+}
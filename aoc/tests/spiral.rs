@@ -0,0 +1,49 @@
+extern crate aoc;
+
+use aoc::geom::Vec2;
+use aoc::spiral;
+
+#[test]
+fn coords() {
+    let first: Vec<_> = (1..11).map(|n| spiral::coord(n).unwrap()).collect();
+    assert_eq!(first, vec![
+        Vec2::new(0, 0), Vec2::new(0, 1), Vec2::new(-1, 1), Vec2::new(-1, 0), Vec2::new(-1, -1),
+        Vec2::new(0, -1), Vec2::new(1, -1), Vec2::new(1, 0), Vec2::new(1, 1), Vec2::new(1, 2),
+    ]);
+    assert_eq!(spiral::coord(0), None);
+    assert_eq!(spiral::coord(12).unwrap().manhattan(), 3);
+    assert_eq!(spiral::coord(23).unwrap().manhattan(), 2);
+    assert_eq!(spiral::coord(1024).unwrap().manhattan(), 31);
+}
+
+#[test]
+fn round_trip() {
+    for n in 1..10_000 {
+        assert_eq!(spiral::index(spiral::coord(n).unwrap()), Some(n));
+    }
+    for y in -20..21 {
+        for x in -20..21 {
+            let p = Vec2::new(y, x);
+            assert_eq!(spiral::coord(spiral::index(p).unwrap()), Some(p));
+        }
+    }
+}
+
+#[test]
+fn large() {
+    // Floats can't tell these apart any more
+    for &n in &[i64::MAX, i64::MAX - 1, (1 << 53) + 1, 3_037_000_499 * 3_037_000_499] {
+        let p = spiral::coord(n).unwrap();
+        assert_eq!(spiral::index(p), Some(n));
+    }
+    assert_eq!(spiral::coord(9_000_000_000_000_000_001).unwrap(), Vec2::new(-1_500_000_000, -1_500_000_000));
+    assert_eq!(spiral::index(Vec2::new(0, i64::MAX)), None);
+    assert_eq!(spiral::index(Vec2::new(i64::MIN, 0)), None);
+}
+
+#[test]
+fn neighbour_sums() {
+    let sums: Vec<_> = spiral::neighbour_sums().take(12).collect();
+    assert_eq!(sums, vec![1, 1, 2, 4, 5, 10, 11, 23, 25, 26, 54, 57]);
+    assert!(spiral::neighbour_sums().last().unwrap() > 1 << 60);
+}
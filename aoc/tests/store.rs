@@ -0,0 +1,54 @@
+extern crate aoc;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use aoc::store::Store;
+
+// A fresh store directory for every test
+fn tempdir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc-store-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn layout() {
+    let store = Store::new("inputs");
+    assert_eq!(store.path(2017, 6), PathBuf::from("inputs/2017/day06.txt"));
+    assert_eq!(store.path(2017, 25), PathBuf::from("inputs/2017/day25.txt"));
+}
+
+#[test]
+fn missing_input() {
+    let dir = tempdir("missing");
+    let store = Store::new(&dir);
+    assert!(!store.contains(2017, 6));
+    let e = store.read(2017, 6).unwrap_err();
+    assert_eq!(e.to_string(), format!("I/O error: No input for 2017 day 6, expected it in {}",
+                                      dir.join("2017/day06.txt").display()));
+}
+
+#[test]
+fn import() {
+    let dir = tempdir("import");
+    let src = dir.join("input.txt");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&src, "0 2 7 0\n").unwrap();
+
+    let store = Store::new(dir.join("cache"));
+    let path = store.import(2017, 6, &src).unwrap();
+    assert_eq!(path, dir.join("cache/2017/day06.txt"));
+    assert!(store.contains(2017, 6));
+    assert_eq!(store.read(2017, 6).unwrap(), "0 2 7 0\n");
+
+    store.write(2017, 6, "4 1 15 12\n").unwrap();
+    assert_eq!(store.read(2017, 6).unwrap(), "4 1 15 12\n");
+
+    assert!(store.import(2017, 7, dir.join("nope.txt")).is_err());
+    assert!(!store.contains(2017, 7));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
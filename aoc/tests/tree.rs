@@ -0,0 +1,103 @@
+extern crate aoc;
+
+use aoc::tree::Fix;
+use aoc::tree::Tree;
+use aoc::tree::Unbalanced;
+
+fn tree(nodes: &[(&str, i64, &[&str])]) -> Result<Tree, aoc::Error> {
+    Tree::new(nodes.iter().map(|&(n, w, c)| (n, w, c.to_vec())))
+}
+
+// The example tower of day 7
+fn tower() -> Tree {
+    tree(&[
+        ("pbga", 66, &[]), ("xhth", 57, &[]), ("ebii", 61, &[]), ("havc", 66, &[]),
+        ("ktlj", 57, &[]), ("fwft", 72, &["ktlj", "cntj", "xhth"]), ("qoyq", 66, &[]),
+        ("padx", 45, &["pbga", "havc", "qoyq"]), ("tknk", 41, &["ugml", "padx", "fwft"]),
+        ("jptl", 61, &[]), ("ugml", 68, &["gyxo", "ebii", "jptl"]), ("gyxo", 61, &[]),
+        ("cntj", 57, &[]),
+    ]).unwrap()
+}
+
+#[test]
+fn structure() {
+    let t = tower();
+    assert_eq!(t.len(), 13);
+    assert_eq!(t.name(t.root()), "tknk");
+    let ugml = t.id("ugml").unwrap();
+    assert_eq!(t.parent(ugml), Some(t.root()));
+    assert_eq!(t.children(ugml).len(), 3);
+    assert_eq!(t.depth(t.id("ebii").unwrap()), 2);
+    assert_eq!(t.weight(ugml), 68);
+    assert_eq!(t.sum(ugml), 251);
+    assert_eq!(t.sum(t.id("padx").unwrap()), 243);
+    assert_eq!(t.sum(t.root()), 41 + 251 + 243 + 243);
+    assert_eq!(t.id("nope"), None);
+}
+
+#[test]
+fn balance() {
+    let t = tower();
+    let ugml = t.id("ugml").unwrap();
+    assert_eq!(t.unbalanced(), vec![Unbalanced{node: t.root(), expected: Some(243), odd: vec![ugml]}]);
+    assert!(t.is_balanced(ugml));
+    assert_eq!(t.fix().unwrap(), Some(Fix{node: ugml, weight: 68, fixed: 60}));
+
+    let t = tree(&[("a", 1, &["b", "c", "d"]), ("b", 2, &[]), ("c", 2, &[]), ("d", 2, &[])]).unwrap();
+    assert_eq!(t.unbalanced(), vec![]);
+    assert_eq!(t.fix().unwrap(), None);
+}
+
+#[test]
+fn deeper() {
+    // d is too heavy, which throws off both b and the root
+    let t = tree(&[
+        ("r", 1, &["a", "b", "c"]),
+        ("a", 7, &[]), ("b", 1, &["d", "e", "f"]), ("c", 7, &[]),
+        ("d", 3, &[]), ("e", 2, &[]), ("f", 2, &[]),
+    ]).unwrap();
+    let unbalanced = t.unbalanced();
+    assert_eq!(unbalanced.len(), 2);
+    assert_eq!(unbalanced[0].node, t.root());
+    assert_eq!(unbalanced[1].odd, vec![t.id("d").unwrap()]);
+    assert_eq!(t.fix().unwrap(), Some(Fix{node: t.id("d").unwrap(), weight: 3, fixed: 2}));
+}
+
+#[test]
+fn unfixable() {
+    let t = tree(&[("r", 1, &["a", "b"]), ("a", 1, &[]), ("b", 2, &[])]).unwrap();
+    assert_eq!(t.unbalanced()[0].expected, None);
+    assert_eq!(t.fix().unwrap_err().to_string(), "Can't solve: Can't tell which child of \"r\" is off");
+
+    // a is off, but b is off inside too
+    let t = tree(&[
+        ("r", 1, &["a", "b", "c"]),
+        ("a", 5, &[]), ("b", 0, &["y", "z", "w"]), ("c", 4, &[]),
+        ("y", 1, &[]), ("z", 1, &[]), ("w", 2, &[]),
+    ]).unwrap();
+    assert_eq!(t.unbalanced().len(), 2);
+    assert_eq!(t.fix().unwrap_err().to_string(), "Can't solve: More than one weight is off");
+
+    // Fixing w would unbalance the root
+    let t = tree(&[
+        ("r", 1, &["a", "b", "c"]),
+        ("a", 4, &[]), ("b", 0, &["y", "z", "w"]), ("c", 4, &[]),
+        ("y", 1, &[]), ("z", 1, &[]), ("w", 2, &[]),
+    ]).unwrap();
+    assert_eq!(t.fix().unwrap_err().to_string(), "Can't solve: No single weight balances the tree");
+}
+
+#[test]
+fn errors() {
+    let e = |nodes: &[(&str, i64, &[&str])]| tree(nodes).unwrap_err().to_string();
+    assert_eq!(e(&[]), "Parse error: Empty tree");
+    assert_eq!(e(&[("a", 1, &["b"])]), "Parse error: Child \"b\" of \"a\" does not exist");
+    assert_eq!(e(&[("a", 1, &[]), ("a", 2, &[])]), "Parse error: Node \"a\" is defined twice");
+    assert_eq!(e(&[("a", 1, &[]), ("b", 1, &[]), ("c", 1, &["d"]), ("d", 1, &[])]),
+               "Parse error: Multiple roots: a, b, c");
+    assert_eq!(e(&[("a", 1, &["c"]), ("b", 1, &["c"]), ("c", 1, &[])]),
+               "Parse error: \"c\" is a child of both \"a\" and \"b\"");
+    assert_eq!(e(&[("a", 1, &["b"]), ("b", 1, &["a"])]), "Parse error: Cycle through \"a\"");
+    assert_eq!(e(&[("r", 1, &["a"]), ("a", 1, &[]), ("b", 1, &["c"]), ("c", 1, &["b"])]),
+               "Parse error: Cycle through \"b\"");
+}
@@ -0,0 +1,149 @@
+extern crate aoc;
+extern crate star01;
+extern crate star03;
+extern crate star05;
+extern crate star07;
+extern crate star09;
+extern crate star11;
+extern crate star13;
+extern crate star15;
+extern crate star17;
+extern crate star19;
+extern crate star21;
+extern crate star23;
+extern crate star25;
+extern crate star27;
+extern crate star29;
+extern crate star31;
+extern crate star33;
+extern crate star35;
+extern crate star37;
+extern crate star39;
+extern crate star41;
+extern crate star43;
+extern crate star45;
+extern crate star47;
+extern crate star49;
+
+use std::time::Duration;
+use std::time::Instant;
+
+use aoc::bench;
+use aoc::bench::Timings;
+use aoc::Error;
+use aoc::Solution;
+
+pub const YEAR: u32 = 2017;
+
+fn no_day(day: u32) -> Error {
+    Error::Solve(format!("There is no day {}", day))
+}
+
+macro_rules! days {
+    ($($day:literal => $solution:ty: $parts:literal,)*) => {
+        pub const DAYS: &[u32] = &[$($day),*];
+
+        // How many parts the puzzle of the day has
+        pub fn parts(day: u32) -> Result<u32, Error> {
+            match day {
+                $($day => Ok($parts),)*
+                d => Err(no_day(d)),
+            }
+        }
+
+        pub fn solve(day: u32, part: u32, input: &str) -> Result<String, Error> {
+            match day {
+                $($day => <$solution>::solve(input, part),)*
+                d => Err(no_day(d)),
+            }
+        }
+
+        pub fn bench(day: u32, input: &str, runs: usize) -> Result<Timings, Error> {
+            match day {
+                $($day => bench::run::<$solution>(input, runs),)*
+                d => Err(no_day(d)),
+            }
+        }
+    }
+}
+
+// Every day with its solution and its number of parts. Day 25 has only one.
+days! {
+    1 => star01::Day01: 2,
+    2 => star03::Day02: 2,
+    3 => star05::Day03: 2,
+    4 => star07::Day04: 2,
+    5 => star09::Day05: 2,
+    6 => star11::Day06: 2,
+    7 => star13::Day07: 2,
+    8 => star15::Day08: 2,
+    9 => star17::Day09: 2,
+    10 => star19::Day10: 2,
+    11 => star21::Day11: 2,
+    12 => star23::Day12: 2,
+    13 => star25::Day13: 2,
+    14 => star27::Day14: 2,
+    15 => star29::Day15: 2,
+    16 => star31::Day16: 2,
+    17 => star33::Day17: 2,
+    18 => star35::Day18: 2,
+    19 => star37::Day19: 2,
+    20 => star39::Day20: 2,
+    21 => star41::Day21: 2,
+    22 => star43::Day22: 2,
+    23 => star45::Day23: 2,
+    24 => star47::Day24: 2,
+    25 => star49::Day25: 1,
+}
+
+// The outcome of solving one part of a day
+#[derive(Debug)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Result<String, Error>,
+    pub elapsed: Duration,
+    pub input_hash: u64,
+}
+
+impl Record {
+    pub fn solve(day: u32, part: u32, input: &str) -> Record {
+        let start = Instant::now();
+        let answer = solve(day, part, input);
+        Record {
+            day,
+            part,
+            answer,
+            elapsed: start.elapsed(),
+            input_hash: aoc::hash::fnv1a(input.as_bytes()),
+        }
+    }
+
+    // A single line JSON object. Failed parts have a null answer and an
+    // error message instead.
+    pub fn to_json(&self) -> String {
+        let (answer, error) = match self.answer {
+            Ok(ref answer) => (json_string(answer), String::new()),
+            Err(ref e) => (String::from("null"), format!(",\"error\":{}", json_string(&e.to_string()))),
+        };
+        format!("{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{}{},\"elapsed_ns\":{},\"input_hash\":\"fnv1a64:{:016x}\"}}",
+                YEAR, self.day, self.part, answer, error, self.elapsed.as_nanos(), self.input_hash)
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
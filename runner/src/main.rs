@@ -0,0 +1,404 @@
+extern crate aoc;
+extern crate runner;
+
+use std::env;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use std::io::Write;
+use std::process;
+
+use aoc::asm;
+use aoc::bench;
+use aoc::bench::Baseline;
+use aoc::bench::Phase;
+use aoc::cfg::Cfg;
+use aoc::debugger::Debugger;
+use aoc::duet::InstructionSet;
+use aoc::duet::Io;
+use aoc::duet::Mailbox;
+use aoc::duet::Sound;
+use aoc::duet::Vm;
+use aoc::hash;
+use aoc::hash::Knot;
+use aoc::input;
+use aoc::log;
+use aoc::store::Store;
+
+fn usage() -> ! {
+    eprintln!("Usage: aoc [-v...] run [--json] <day|all> <part|all> [input]");
+    eprintln!("       aoc [-v...] import <day> <input>");
+    eprintln!("       aoc [-v...] bench [--runs <n>] [--baseline <file>] [--save <file>] [day...]");
+    eprintln!("       aoc [-v...] knot [--hex|--binary|--grid] [--file <file>...] [string...]");
+    eprintln!("       aoc [-v...] knot --check");
+    eprintln!("       aoc [-v...] asm [--set <duet|coprocessor|full>] [source]");
+    eprintln!("       aoc [-v...] cfg [--set <duet|coprocessor|full>] [--dot] [source]");
+    eprintln!("       aoc [-v...] debug [--set <...>] [--io <none|sound|messages>] [--break <pc|label>...] <source>");
+    eprintln!();
+    eprintln!("Every -v shows more diagnostics on stderr, up to -vvv or --trace.");
+    process::exit(2);
+}
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    s.parse().map_err(|_| format!("Invalid day \"{}\"", s))
+}
+
+// Reads the input from the given file, stdin for "-", or the input store if
+// no file is given
+fn read_input(day: u32, path: Option<&str>) -> Result<String, String> {
+    match path {
+        None => Store::from_env().read(runner::YEAR, day).map_err(|e| e.to_string()),
+        Some(path) => input::read(path).map_err(|e| format!("Can't read input: {}", e)),
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let json = args.iter().any(|a| a == "--json");
+    let args: Vec<_> = args.iter().filter(|a| *a != "--json").collect();
+    if args.len() < 2 || args.len() > 3 {
+        usage();
+    }
+
+    let days = match args[0].as_str() {
+        "all" => runner::DAYS.to_vec(),
+        day => vec![parse_day(day)?],
+    };
+    let part = match args[1].as_str() {
+        "all" => None,
+        part => Some(part.parse().map_err(|_| format!("Invalid part \"{}\"", part))?),
+    };
+    let path = args.get(2).map(|s| s.as_str());
+    if days.len() > 1 && path.is_some() {
+        return Err(String::from("Every day needs its own input, leave it out to use the input store"));
+    }
+
+    let many = days.len() > 1 || part.is_none();
+    let mut failed = false;
+    for &day in &days {
+        let parts = match part {
+            Some(part) => vec![part],
+            None => (1..=runner::parts(day).map_err(|e| e.to_string())?).collect(),
+        };
+        let input = read_input(day, path)?;
+        for &part in &parts {
+            let record = runner::Record::solve(day, part, &input);
+            if json {
+                println!("{}", record.to_json());
+            }
+            match record.answer {
+                Ok(ref answer) if !json => if many {
+                    println!("{} {} {}", day, part, answer);
+                } else {
+                    println!("{}", answer);
+                },
+                Ok(_) => (),
+                Err(ref e) => {
+                    failed = true;
+                    eprintln!("Day {} part {}: {}", day, part, e);
+                }
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn import(args: &[String]) -> Result<(), String> {
+    if args.len() != 2 {
+        usage();
+    }
+
+    let day = parse_day(&args[0])?;
+    let path = Store::from_env().import(runner::YEAR, day, &args[1]).map_err(|e| e.to_string())?;
+    println!("{}", path.display());
+    Ok(())
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let mut runs = 10;
+    let mut baseline = None;
+    let mut save = None;
+    let mut days = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let n = args.next().unwrap_or_else(|| usage());
+                runs = n.parse().map_err(|_| format!("Invalid number of runs \"{}\"", n))?;
+            }
+            "--baseline" => {
+                let path = args.next().unwrap_or_else(|| usage());
+                let s = input::read(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+                let b: Baseline = s.parse().map_err(|e| format!("{}: {}", path, e))?;
+                baseline = Some(b);
+            }
+            "--save" => save = Some(args.next().unwrap_or_else(|| usage())),
+            day => days.push(parse_day(day)?),
+        }
+    }
+    if days.is_empty() {
+        days = runner::DAYS.to_vec();
+    }
+
+    print!("{:>3} {:5} {:>5} {:>9} {:>9} {:>9} {:>9} {:>9}", "day", "phase", "runs",
+           "min", "median", "mean", "max", "stddev");
+    if baseline.is_some() {
+        print!(" {:>9} {:>8}", "baseline", "change");
+    }
+    println!();
+
+    let mut measured = Baseline::new();
+    let mut failures = Vec::new();
+    for &day in &days {
+        let timings = match read_input(day, None).and_then(|input| {
+            runner::bench(day, &input, runs).map_err(|e| e.to_string())
+        }) {
+            Ok(timings) => timings,
+            Err(e) => {
+                failures.push(format!("day {}: {}", day, e));
+                continue;
+            }
+        };
+        measured.add(day, &timings);
+
+        // Parsing and then every part the day has
+        let phases = runner::parts(day).map(|n| n as usize + 1).unwrap_or(0);
+        for &phase in Phase::all().iter().take(phases) {
+            let stats = match timings.get(phase) {
+                Ok(stats) => stats,
+                Err(e) => {
+                    failures.push(format!("day {} {}: {}", day, phase, e));
+                    continue;
+                }
+            };
+            print!("{:>3} {:5} {:>5} {:>9} {:>9} {:>9} {:>9} {:>9}", day, phase, stats.runs,
+                   bench::human(stats.min), bench::human(stats.median), bench::human(stats.mean),
+                   bench::human(stats.max), bench::human(stats.stddev));
+            if let Some(ref baseline) = baseline {
+                match baseline.get(day, phase) {
+                    Some(base) => print!(" {:>9} {:>8}", bench::human(base),
+                                         bench::change(base, stats.median)),
+                    None => print!(" {:>9} {:>8}", "-", "-"),
+                }
+            }
+            println!();
+        }
+    }
+
+    for failure in &failures {
+        eprintln!("{}", failure);
+    }
+
+    if let Some(path) = save {
+        fs::write(path, measured.to_string()).map_err(|e| format!("Can't write {}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+// Hashes the strings and files given, or every line of stdin without them
+fn knot(args: &[String]) -> Result<(), String> {
+    let mut format: fn(&Knot) -> String = Knot::to_string;
+    let mut check = false;
+    let mut inputs = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--hex" => format = Knot::to_string,
+            "--binary" => format = Knot::to_binary,
+            "--grid" => format = Knot::to_grid,
+            "--check" => check = true,
+            "--file" => {
+                let path = args.next().unwrap_or_else(|| usage());
+                let s = input::read(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+                inputs.push(s.trim_end_matches(&['\n', '\r'][..]).to_string());
+            }
+            s => inputs.push(s.to_string()),
+        }
+    }
+
+    if check {
+        if !inputs.is_empty() {
+            usage();
+        }
+        let mut failed = false;
+        for &(input, expected) in &hash::EXAMPLES {
+            let got = Knot::hash(input.as_bytes()).to_string();
+            if got == expected {
+                println!("ok   {:?} {}", input, got);
+            } else {
+                failed = true;
+                println!("FAIL {:?} {}, expected {}", input, got, expected);
+            }
+        }
+        if failed {
+            return Err(String::from("Knot hash self-check failed"));
+        }
+        return Ok(());
+    }
+
+    if inputs.is_empty() {
+        let stdin = io::stdin();
+        for line in stdin.lock().lines() {
+            let line = line.map_err(|e| format!("Can't read stdin: {}", e))?;
+            println!("{}", format(&Knot::hash(line.as_bytes())));
+        }
+    } else {
+        for input in &inputs {
+            println!("{}", format(&Knot::hash(input.as_bytes())));
+        }
+    }
+    Ok(())
+}
+
+fn instruction_set(name: &str) -> Result<InstructionSet, String> {
+    match name {
+        "duet" => Ok(InstructionSet::duet()),
+        "coprocessor" => Ok(InstructionSet::coprocessor()),
+        "full" => Ok(InstructionSet::full()),
+        _ => Err(format!("Unknown instruction set \"{}\"", name)),
+    }
+}
+
+// Assembles a duet program from the file or stdin and prints it as plain
+// instructions
+fn assemble(args: &[String]) -> Result<(), String> {
+    let mut set = InstructionSet::full();
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--set" => set = instruction_set(args.next().unwrap_or_else(|| usage()))?,
+            s if path.is_none() => path = Some(s),
+            _ => usage(),
+        }
+    }
+
+    let path = path.unwrap_or("-");
+    let source = input::read(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+    let program = asm::assemble(&set, &source).map_err(|e| format!("{}: {}", path, e))?;
+    print!("{}", program.listing());
+    Ok(())
+}
+
+// Splits a duet program into basic blocks and prints them as an annotated
+// listing, or as a Graphviz graph
+fn cfg(args: &[String]) -> Result<(), String> {
+    let mut set = InstructionSet::full();
+    let mut dot = false;
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--set" => set = instruction_set(args.next().unwrap_or_else(|| usage()))?,
+            "--dot" => dot = true,
+            s if path.is_none() => path = Some(s),
+            _ => usage(),
+        }
+    }
+
+    let path = path.unwrap_or("-");
+    let source = input::read(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+    let program = asm::assemble(&set, &source).map_err(|e| format!("{}: {}", path, e))?;
+    let cfg = Cfg::from_program(&program);
+    if dot {
+        print!("{}", cfg.dot(&program.set));
+    } else {
+        print!("{}", cfg.listing(&program.set));
+    }
+    Ok(())
+}
+
+// Reads debugger commands from stdin until quit or the end of input
+fn debug_loop<T: Io>(mut dbg: Debugger<T>, breaks: &[String]) -> Result<(), String> {
+    for b in breaks {
+        let reply = dbg.command(&format!("break {}", b)).map_err(|e| e.to_string())?;
+        println!("{}", reply);
+    }
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("(duet) ");
+        io::stdout().flush().map_err(|e| e.to_string())?;
+        let line = match lines.next() {
+            Some(line) => line.map_err(|e| format!("Can't read stdin: {}", e))?,
+            None => break,
+        };
+        if line.trim() == "q" || line.trim() == "quit" {
+            break;
+        }
+        match dbg.command(&line) {
+            Ok(reply) => println!("{}", reply),
+            Err(e) => println!("{}", e),
+        }
+    }
+    println!();
+    Ok(())
+}
+
+// Runs a duet program under the debugger, taking commands from stdin. With
+// message passing there are two machines, each with its id in p.
+fn debug(args: &[String]) -> Result<(), String> {
+    let mut set = InstructionSet::full();
+    let mut io = String::from("none");
+    let mut breaks = Vec::new();
+    let mut path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--set" => set = instruction_set(args.next().unwrap_or_else(|| usage()))?,
+            "--io" => io = args.next().unwrap_or_else(|| usage()).clone(),
+            "--break" => breaks.push(args.next().unwrap_or_else(|| usage()).clone()),
+            s if path.is_none() => path = Some(s),
+            _ => usage(),
+        }
+    }
+
+    // Commands come from stdin, so the program has to be in a file
+    let path = path.unwrap_or_else(|| usage());
+    let source = input::read(path).map_err(|e| format!("Can't read {}: {}", path, e))?;
+    let program = asm::assemble(&set, &source).map_err(|e| format!("{}: {}", path, e))?;
+    let (set, code) = (&program.set, &program.code);
+    match io.as_str() {
+        "none" => debug_loop(Debugger::new(program.clone(), vec![Vm::new(set, code, ())]), &breaks),
+        "sound" => debug_loop(Debugger::new(program.clone(), vec![Vm::new(set, code, Sound::new())]), &breaks),
+        "messages" => {
+            let mut vms = vec![Vm::new(set, code, Mailbox::new()), Vm::new(set, code, Mailbox::new())];
+            if let Some(p) = set.register("p") {
+                for (id, vm) in vms.iter_mut().enumerate() {
+                    vm.set_reg(p, id as i64);
+                }
+            }
+            debug_loop(Debugger::new(program.clone(), vms), &breaks)
+        }
+        _ => Err(format!("Unknown io \"{}\", expected none, sound or messages", io)),
+    }
+}
+
+fn main() {
+    log::init();
+    let args: Vec<String> = env::args().skip(1).filter(|a| log::flag(a).is_none()).collect();
+
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("import") => import(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("knot") => knot(&args[1..]),
+        Some("asm") => assemble(&args[1..]),
+        Some("cfg") => cfg(&args[1..]),
+        Some("debug") => debug(&args[1..]),
+        _ => usage(),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,45 @@
+extern crate aoc;
+extern crate runner;
+
+use std::time::Duration;
+
+use aoc::Error;
+use runner::Record;
+
+#[test]
+fn strings() {
+    assert_eq!(runner::json_string("abc"), "\"abc\"");
+    assert_eq!(runner::json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+}
+
+#[test]
+fn input_hash() {
+    assert_eq!(aoc::hash::fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(aoc::hash::fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+}
+
+#[test]
+fn records() {
+    let mut record = Record {
+        day: 6,
+        part: 1,
+        answer: Ok(String::from("5")),
+        elapsed: Duration::from_micros(12),
+        input_hash: 0x42,
+    };
+    assert_eq!(record.to_json(), "{\"year\":2017,\"day\":6,\"part\":1,\"answer\":\"5\",\
+                                  \"elapsed_ns\":12000,\"input_hash\":\"fnv1a64:0000000000000042\"}");
+
+    record.answer = Err(Error::Solve(String::from("No \"answer\"")));
+    assert_eq!(record.to_json(), "{\"year\":2017,\"day\":6,\"part\":1,\"answer\":null,\
+                                  \"error\":\"Can't solve: No \\\"answer\\\"\",\
+                                  \"elapsed_ns\":12000,\"input_hash\":\"fnv1a64:0000000000000042\"}");
+}
+
+#[test]
+fn solve() {
+    let record = Record::solve(6, 2, "0 2 7 0");
+    assert_eq!(record.answer, Ok(String::from("4")));
+    assert_eq!(record.input_hash, aoc::hash::fnv1a(b"0 2 7 0"));
+    assert!(Record::solve(26, 1, "").answer.is_err());
+}
@@ -0,0 +1,163 @@
+extern crate runner;
+
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+
+enum Input {
+    File(String),
+    Literal(String),
+}
+
+struct Case {
+    line: usize,
+    day: u32,
+    part: u32,
+    input: Input,
+    answer: String,
+}
+
+fn root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn read(path: &PathBuf) -> String {
+    let mut s = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut s))
+        .unwrap_or_else(|e| panic!("Can't read {}: {}", path.display(), e));
+    s
+}
+
+impl Input {
+    fn read(&self) -> String {
+        match *self {
+            Input::File(ref path) => read(&root().join(path)),
+            Input::Literal(ref s) => s.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            Input::File(ref path) => write!(f, "{}", path),
+            Input::Literal(ref s) => write!(f, "{:?}", s),
+        }
+    }
+}
+
+// Parses a quoted string at the start of s and returns it together with the
+// rest of the line
+fn literal(s: &str) -> Result<(String, &str), String> {
+    let mut ret = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((ret, &s[i+1..])),
+            '\\' => match chars.next() {
+                Some((_, 'n')) => ret.push('\n'),
+                Some((_, c)) if c == '"' || c == '\\' => ret.push(c),
+                _ => return Err(String::from("Invalid escape sequence")),
+            },
+            c => ret.push(c),
+        }
+    }
+    Err(String::from("Unterminated string"))
+}
+
+fn parse_case(line: usize, s: &str) -> Result<Case, String> {
+    let mut fields = s.splitn(3, char::is_whitespace);
+    let day = fields.next().unwrap().parse().map_err(|_| "Invalid day")?;
+    let part = fields.next().ok_or("Missing part")?.parse().map_err(|_| "Invalid part")?;
+    let rest = fields.next().ok_or("Missing input")?.trim_start();
+
+    let (input, rest) = if rest.starts_with('"') {
+        let (s, rest) = literal(rest)?;
+        (Input::Literal(s), rest)
+    } else {
+        let mut split = rest.splitn(2, char::is_whitespace);
+        let path = split.next().unwrap().to_string();
+        (Input::File(path), split.next().unwrap_or(""))
+    };
+
+    let answer = rest.trim().to_string();
+    if answer.is_empty() {
+        return Err(String::from("Missing answer"));
+    }
+
+    Ok(Case{line, day, part, input, answer})
+}
+
+fn cases() -> Vec<Case> {
+    let answers = read(&root().join("runner/tests/answers.txt"));
+    answers.lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|&(_, l)| !l.is_empty() && !l.starts_with('#'))
+        .map(|(i, l)| parse_case(i, l).unwrap_or_else(|e| panic!("answers.txt:{}: {}", i, e)))
+        .collect()
+}
+
+fn check(day: u32) {
+    let cases: Vec<_> = cases().into_iter().filter(|c| c.day == day).collect();
+    assert!(!cases.is_empty(), "No known answers for day {}", day);
+    for part in 1..=runner::parts(day).unwrap() {
+        assert!(cases.iter().any(|c| c.part == part), "No known answers for day {} part {}", day, part);
+    }
+
+    let mut regressions = Vec::new();
+    for case in &cases {
+        let result = runner::solve(case.day, case.part, &case.input.read());
+        let got = match result {
+            Ok(ref answer) if *answer == case.answer => continue,
+            Ok(answer) => answer,
+            Err(e) => e.to_string(),
+        };
+        regressions.push(format!("answers.txt:{}: day {} part {} with {}: expected {}, got {}",
+                                 case.line, case.day, case.part, case.input, case.answer, got));
+    }
+
+    if !regressions.is_empty() {
+        panic!("Day {} regressed:\n{}", day, regressions.join("\n"));
+    }
+}
+
+macro_rules! days {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check($day);
+            }
+        )*
+    }
+}
+
+days! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+    day18: 18,
+    day19: 19,
+    day20: 20,
+    day21: 21,
+    day22: 22,
+    day23: 23,
+    day24: 24,
+    day25: 25,
+}
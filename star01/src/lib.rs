@@ -0,0 +1,38 @@
+extern crate aoc;
+
+use aoc::Error;
+use aoc::Solution;
+
+fn captcha(digits: &[u32], offset: usize) -> u32 {
+    let i1 = digits.iter();
+    let i2 = digits.iter().cycle().skip(offset);
+
+    i1.zip(i2).fold(0u32, |sum, (&x1, &x2)| sum + if x1 == x2 { x1 } else { 0u32 })
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, Error> {
+        aoc::map_line(input, |line| {
+            let line = line.trim();
+            line.char_indices()
+                .map(|(i, c)| c.to_digit(10).ok_or_else(|| {
+                    Error::token(line, &line[i..i + c.len_utf8()], "Only digits are allowed")
+                }))
+                .collect()
+        })
+    }
+
+    fn part1(digits: &Vec<u32>) -> Result<u32, Error> {
+        Ok(captcha(digits, 1))
+    }
+
+    fn part2(digits: &Vec<u32>) -> Result<u32, Error> {
+        Ok(captcha(digits, digits.len() / 2))
+    }
+}
@@ -0,0 +1,49 @@
+extern crate aoc;
+
+use std::cmp;
+use aoc::Error;
+use aoc::Solution;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, Error> {
+        aoc::map_lines(input, |line| {
+            line.split_whitespace()
+                .map(|s| aoc::parse_token(line, s))
+                .collect()
+        })
+    }
+
+    fn part1(rows: &Vec<Vec<i32>>) -> Result<i32, Error> {
+        let mut sum = 0i32;
+        for row in rows {
+            let min = row.iter().min().ok_or("Empty row")?;
+            let max = row.iter().max().ok_or("Empty row")?;
+            sum += max - min;
+        }
+        Ok(sum)
+    }
+
+    fn part2(rows: &Vec<Vec<i32>>) -> Result<i32, Error> {
+        let mut sum = 0i32;
+        for v in rows {
+            let mut iter = v.iter();
+            for x1 in v.iter() {
+                if iter.next().is_none() { break; }
+                for x2 in iter.clone() {
+                    let (x1, x2): (i32, i32) = (*cmp::max(x1, x2), *cmp::min(x1,x2));
+                    let div = x1 / x2;
+                    if div > 0 && div * x2 == x1 {
+                        sum += div;
+                    }
+                }
+            }
+        }
+        Ok(sum)
+    }
+}
@@ -1,30 +1,9 @@
-use std::io;
-use std::io::BufRead;
+extern crate aoc;
+extern crate star03;
 
-fn main() {
-    let mut sum = 0i32;
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let mut min = i32::max_value();
-        let mut max = i32::min_value();
-        for num in line.unwrap().split_whitespace().map(|s| s.parse::<i32>()) {
-            match num {
-                Ok(x) => {
-                    if x < min {
-                        min = x;
-                    }
-                    if x > max {
-                        max = x;
-                    }
-                },
-                Err(error) => panic!(error),
-            }
-        }
-        if max < min {
-            panic!("{} < {}?", max, min);
-        }
-        sum += max - min;
-    }
+use aoc::Solution;
+use star03::Day02;
 
-    println!("sum: {}", sum);
+fn main() {
+    aoc::main(Day02::parse, |input| Day02::part1(input).map(|a| format!("sum: {}", a)));
 }
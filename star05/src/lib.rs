@@ -0,0 +1,40 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::spiral;
+use aoc::Error;
+use aoc::Solution;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = i64;
+    type Part1 = i64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<i64, Error> {
+        aoc::map_line(input, |line| {
+            let n: i64 = aoc::parse_token(line, line.trim())?;
+            if n < 1 {
+                return Err(Error::token(line, line.trim(), "Squares are numbered from 1"));
+            }
+            Ok(n)
+        })
+    }
+
+    fn part1(n: &i64) -> Result<i64, Error> {
+        let coords = spiral::coord(*n)
+            .ok_or_else(|| Error::Solve(format!("There is no square {}, they are numbered from 1", n)))?;
+        debug!("location: {:?}", coords);
+        Ok(coords.manhattan())
+    }
+
+    fn part2(n: &i64) -> Result<u64, Error> {
+        spiral::neighbour_sums()
+            .enumerate()
+            .inspect(|&(i, sum)| trace!("{:3}: {:6}", i + 1, sum))
+            .map(|(_, sum)| sum)
+            .find(|&sum| sum > *n as u64)
+            .ok_or_else(|| Error::Solve(format!("No value larger than {} found", n)))
+    }
+}
@@ -0,0 +1,64 @@
+#[macro_use]
+extern crate aoc;
+
+use std::collections::HashSet;
+use aoc::Error;
+use aoc::Solution;
+
+fn check_pass(pw: &str) -> bool {
+    let mut set = HashSet::new();
+    for word in pw.split_whitespace() {
+        if !set.insert(word) {
+            return false;
+        }
+    }
+    true
+}
+
+fn check_anagrams(pw: &str) -> bool {
+    let mut set = HashSet::new();
+    for word in pw.split_whitespace() {
+        let mut pw: Vec<char> = word.chars().collect::<Vec<char>>();
+        pw.sort();
+        if !set.insert(pw) {
+            return false;
+        }
+    }
+    true
+}
+
+fn count_valid(passphrases: &[String], check: fn(&str) -> bool) -> u32 {
+    let mut num_valid = 0u32;
+    for line in passphrases {
+        let valid = check(line);
+
+        trace!("{:5}: {}", valid, line);
+        if valid {
+            num_valid += 1;
+        }
+    }
+    num_valid
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        Ok(input.lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    fn part1(passphrases: &Vec<String>) -> Result<u32, Error> {
+        Ok(count_valid(passphrases, check_pass))
+    }
+
+    fn part2(passphrases: &Vec<String>) -> Result<u32, Error> {
+        Ok(count_valid(passphrases, check_anagrams))
+    }
+}
@@ -1,35 +1,9 @@
-use std::io;
-use std::io::BufRead;
-use std::collections::HashSet;
+extern crate aoc;
+extern crate star07;
 
-fn check_pass(pw: &String) -> bool {
-    let mut set = HashSet::new();
-    for word in pw.split_whitespace() {
-        let mut pw: Vec<char> = word.chars().collect::<Vec<char>>();
-        pw.sort();
-        if set.insert(pw) == false {
-            return false
-        }
-    }
-    true
-}
+use aoc::Solution;
+use star07::Day04;
 
 fn main() {
-    let mut num_valid = 0u32;
-    let stdin = io::stdin();
-    for line in stdin.lock().lines() {
-        let line = line.expect("Oo?");
-        
-        if line.trim().len() == 0 {
-            continue;
-        }
-
-        let valid = check_pass(&line);
-
-        println!("{:5}: {}", valid, line);
-        if valid {
-            num_valid += 1;
-        }
-    }
-    println!("Number of valid passwords: {}", num_valid);
+    aoc::main(Day04::parse, |input| Day04::part2(input).map(|a| format!("Number of valid passwords: {}", a)));
 }
@@ -0,0 +1,38 @@
+extern crate aoc;
+
+use aoc::Error;
+use aoc::Solution;
+
+fn jump(mut table: Vec<isize>, update: fn(isize) -> isize) -> isize {
+    let mut pos = 0isize;
+    let mut count = 0isize;
+
+    while 0 <= pos && pos < table.len() as isize{
+        let t = table[pos as usize];
+        table[pos as usize] = update(t);
+        pos += t;
+        count += 1;
+    }
+
+    count
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<isize>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Vec<isize>, Error> {
+        aoc::map_lines(input, |line| aoc::parse_token(line, line.trim()))
+    }
+
+    fn part1(table: &Vec<isize>) -> Result<isize, Error> {
+        Ok(jump(table.clone(), |t| t + 1))
+    }
+
+    fn part2(table: &Vec<isize>) -> Result<isize, Error> {
+        Ok(jump(table.clone(), |t| if t >= 3 { t - 1 } else { t + 1 }))
+    }
+}
@@ -1,25 +1,9 @@
-use std::io;
-use std::io::BufRead;
+extern crate aoc;
+extern crate star09;
 
-fn main() {
-    let mut pos = 0isize;
-    let mut count = 0isize;
-    let stdin = io::stdin();
-    let mut table: Vec<isize> = stdin.lock()
-        .lines()
-        .map(
-            |s| s.expect("Oo?")
-                .parse()
-                .expect("Not a number!")
-            )
-        .collect();
-
-    while 0 <= pos && pos < table.len() as isize{
-        let t = table[pos as usize];
-        table[pos as usize] += 1;
-        pos += t;
-        count += 1;
-    }
+use aoc::Solution;
+use star09::Day05;
 
-    println!("{} steps taken", count);
+fn main() {
+    aoc::main(Day05::parse, |input| Day05::part1(input).map(|a| format!("{} steps taken", a)));
 }
@@ -0,0 +1,100 @@
+#[macro_use]
+extern crate aoc;
+
+use std::collections::BTreeSet;
+use aoc::Error;
+use aoc::Solution;
+
+#[derive(Clone,PartialEq,Eq,Debug,PartialOrd,Ord,Default)]
+pub struct State {
+    banks: Vec<u8>,
+}
+
+impl From<Vec<u8>> for State {
+    fn from(banks: Vec<u8>) -> State {
+        State{banks}
+    }
+}
+
+impl State {
+    fn find_max(&self) -> usize {
+        let mut max = self.banks[0];
+        let mut max_index = 0usize;
+
+        for (i, bank) in self.banks.iter().enumerate() {
+            if *bank > max {
+                max = *bank;
+                max_index = i;
+            }
+        }
+
+        max_index
+    }
+}
+
+impl Iterator for State {
+    type Item = State;
+
+    fn next(&mut self) -> Option<State> {
+        let mut idx = self.find_max();
+        let mut n = self.banks[idx];
+
+        self.banks[idx] = 0;
+        while n > 0 {
+            idx = (idx + 1) % self.banks.len();
+            self.banks[idx] += 1;
+            n -= 1;
+        }
+
+        Some(self.clone())
+    }
+}
+
+// Redistributes until a state repeats. Returns the number of distinct states
+// seen and the position of the first occurrence of the repeated state.
+fn find_cycle(start: &State) -> (usize, usize) {
+    let mut states = BTreeSet::new();
+    let mut history = Vec::new();
+    states.insert(start.clone());
+    history.push(start.clone());
+
+    for state in start.clone() {
+        trace!("{:?}", state);
+        history.push(state.clone());
+        if !states.insert(state) {
+            break;
+        }
+    }
+    let last = history.last().unwrap();
+    let pos = history.iter().position(|s| *s == *last).unwrap();
+    (states.len(), pos)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = State;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<State, Error> {
+        let start: Vec<u8> = aoc::map_line(input, |line| {
+            line.split_whitespace()
+                .map(|s| aoc::parse_token(line, s))
+                .collect()
+        })?;
+        if start.is_empty() {
+            return Err(Error::from("No memory banks"));
+        }
+        Ok(State::from(start))
+    }
+
+    fn part1(start: &State) -> Result<usize, Error> {
+        Ok(find_cycle(start).0)
+    }
+
+    fn part2(start: &State) -> Result<usize, Error> {
+        let (seen, pos) = find_cycle(start);
+        Ok(seen - pos)
+    }
+}
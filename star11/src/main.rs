@@ -1,81 +1,9 @@
-use std::io;
-use std::io::BufRead;
-use std::collections::BTreeSet;
+extern crate aoc;
+extern crate star11;
 
-#[derive(Copy,Clone,PartialEq,Eq,Debug,PartialOrd,Ord,Default)]
-struct State {
-    banks: [u8; 16],
-}
-
-impl From<Vec<u8>> for State {
-    fn from(s: Vec<u8>) -> State {
-        let mut ret = State::default();
-        for (from, to) in s.iter().zip(ret.banks.iter_mut()) {
-            *to = *from;
-        }
-        ret
-    }
-}
-
-impl State {
-    fn find_max(&self) -> usize {
-        let mut max = self.banks[0];
-        let mut max_index = 0usize;
-
-        for (i, bank) in self.banks.iter().enumerate() {
-            if *bank > max {
-                max = *bank;
-                max_index = i;
-            }
-        }
-
-        max_index
-    }
-}
-
-impl Iterator for State {
-    type Item = State;
-
-    fn next(&mut self) -> Option<State> {
-        let mut idx = self.find_max();
-        let mut n = self.banks[idx];
-
-        self.banks[idx] = 0;
-        while n > 0 {
-            idx = (idx + 1) % self.banks.len();
-            self.banks[idx] += 1;
-            n -= 1;
-        }
-
-        Some(self.clone())
-    }
-}
+use aoc::Solution;
+use star11::Day06;
 
 fn main() {
-    let stdin = io::stdin();
-    let start: Vec<u8> = stdin.lock()
-        .lines()
-        .next()
-        .unwrap()
-        .unwrap()
-        .split_whitespace()
-        .map(
-            |s| s.parse()
-                .expect("Not a number!")
-            )
-        .collect();
-
-    let start = State::from(start);
-
-    let mut states = BTreeSet::new();
-    states.insert(start);
-
-    for state in start {
-        println!("{:?}", state);
-        if !states.insert(state) {
-            break;
-        }
-    }
-    println!("Found duplicate! {}", states.len());
-
+    aoc::main(Day06::parse, |input| Day06::part1(input).map(|a| format!("Found duplicate! {}", a)));
 }
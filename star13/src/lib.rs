@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::tree::Tree;
+use aoc::Error;
+use aoc::Solution;
+
+// Parses "name (weight) -> child, child"
+fn parse_node(line: &str) -> Result<(String, i64, Vec<String>), Error> {
+    let v: Vec<&str> = line.split(" -> ").collect();
+    if v.len() > 2 {
+        return Err(Error::token(line, v[2], "Unexpected \"->\""));
+    }
+
+    let nodestr: Vec<&str> = v[0].split_whitespace().collect();
+    if nodestr.len() != 2 {
+        return Err(Error::token(line, v[0].trim(), "Expected <name> (<weight>)"));
+    }
+
+    let name = nodestr[0];
+    let weight = nodestr[1];
+    if !weight.starts_with('(') || !weight.ends_with(')') {
+        return Err(Error::token(line, weight, "Expected weight in parentheses"));
+    }
+    let weight: i64 = aoc::parse_token(line, &weight[1..weight.len()-1])?;
+
+    let mut children = Vec::new();
+    if v.len() == 2 {
+        for to in v[1].split(',').map(|s| s.trim()) {
+            if to.is_empty() || to.contains(char::is_whitespace) {
+                return Err(Error::token(line, to, "Invalid child name"));
+            }
+            children.push(to.to_string());
+        }
+    }
+    Ok((name.to_string(), weight, children))
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Tree;
+    type Part1 = String;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Tree, Error> {
+        Tree::new(aoc::map_lines(input, parse_node)?)
+    }
+
+    fn part1(tree: &Tree) -> Result<String, Error> {
+        Ok(tree.name(tree.root()).to_string())
+    }
+
+    fn part2(tree: &Tree) -> Result<i64, Error> {
+        info!("root: {}", tree.name(tree.root()));
+        for u in tree.unbalanced() {
+            let children: Vec<_> = tree.children(u.node).iter()
+                .map(|&c| format!("{} ({})", tree.name(c), tree.sum(c)))
+                .collect();
+            debug!("Unbalanced {}: {}", tree.name(u.node), children.join(", "));
+        }
+        let fix = tree.fix()?.ok_or_else(|| Error::Solve(String::from("Tower is balanced")))?;
+        debug!("Fixing {}: {} -> {}", tree.name(fix.node), fix.weight, fix.fixed);
+        Ok(fix.fixed)
+    }
+}
@@ -0,0 +1,174 @@
+#[macro_use]
+extern crate aoc;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use aoc::Error;
+use aoc::Solution;
+
+enum Dir {
+    Inc,
+    Dec,
+}
+
+struct Op {
+    kind: Dir,
+    reg: String,
+    imm: i32
+}
+
+impl FromStr for Op {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        if tokens.len() != 3 {
+            return Err(Error::token(s, s.trim(), "Expected <register> inc|dec <value>"));
+        }
+
+        let reg = tokens[0].to_owned();
+        let kind = match tokens[1] {
+            "inc" => Dir::Inc,
+            "dec" => Dir::Dec,
+            op => return Err(Error::token(s, op, "Unknown opcode")),
+        };
+        let imm = aoc::parse_token(s, tokens[2])?;
+        Ok(Op{kind, reg, imm})
+    }
+}
+
+enum Cond {
+    Eq,
+    Ge,
+    Gt,
+    Le,
+    Lt,
+    Ne,
+}
+
+struct OpCond {
+    kind: Cond,
+    reg: String,
+    imm: i32,
+}
+
+impl FromStr for OpCond {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        if tokens.len() != 3 {
+            return Err(Error::token(s, s.trim(), "Expected <register> <comparator> <value>"));
+        }
+
+        let reg = tokens[0].to_owned();
+        let kind = match tokens[1] {
+            "==" => Cond::Eq,
+            ">=" => Cond::Ge,
+            ">"  => Cond::Gt,
+            "<=" => Cond::Le,
+            "<"  => Cond::Lt,
+            "!=" => Cond::Ne,
+            cmp => return Err(Error::token(s, cmp, "Unknown comparator")),
+        };
+        let imm = aoc::parse_token(s, tokens[2])?;
+        Ok(OpCond{kind, reg, imm})
+    }
+}
+
+
+pub struct Ins {
+    op: Op,
+    cond: OpCond,
+}
+
+impl FromStr for Ins {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split(" if ").collect();
+        if tokens.len() != 2 {
+            return Err(Error::token(s, s.trim(), "Expected <operation> if <condition>"))
+        }
+
+        Ok(Ins{
+            op: tokens[0].parse().map_err(|e: Error| e.within(s, tokens[0]))?,
+            cond: tokens[1].parse().map_err(|e: Error| e.within(s, tokens[1]))?,
+        })
+    }
+    
+}
+
+#[derive(Debug)]
+struct MachineState {
+    regs: HashMap<String, i32>,
+}
+
+impl MachineState {
+    fn exec(&mut self, ins: &Ins) {
+        let reg = self.get(&ins.cond.reg);
+        let run = match ins.cond.kind {
+            Cond::Eq => reg == ins.cond.imm,
+            Cond::Ge => reg >= ins.cond.imm,
+            Cond::Gt => reg >  ins.cond.imm,
+            Cond::Le => reg <= ins.cond.imm,
+            Cond::Lt => reg <  ins.cond.imm,
+            Cond::Ne => reg != ins.cond.imm,
+        };
+        if !run {
+            return;
+        }
+        let reg = self.get(&ins.op.reg);
+        self.set(&ins.op.reg,
+            match ins.op.kind {
+                Dir::Inc => reg + ins.op.imm,
+                Dir::Dec => reg - ins.op.imm,
+            }
+        )
+    }
+
+    fn get(&mut self, key: &str) -> i32 {
+        *self.regs.entry(key.to_owned()).or_insert(0)
+    }
+    fn set(&mut self, key: &str, val: i32) {
+        self.regs.insert(key.to_owned(), val);
+    }
+}
+
+// Runs the program and returns the final state together with the largest
+// value held in any register during the run.
+fn run(program: &[Ins]) -> (MachineState, i32) {
+    let mut ms = MachineState{regs:HashMap::new()};
+    let mut max = 0i32;
+    for op in program {
+        ms.exec(op);
+        trace!("{:?}", ms);
+
+        let m = *ms.regs.values().max().unwrap();
+        if m > max {
+            max = m;
+        }
+    }
+    (ms, max)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<Ins>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<Ins>, Error> {
+        aoc::parse_lines(input)
+    }
+
+    fn part1(program: &Vec<Ins>) -> Result<i32, Error> {
+        let (ms, _) = run(program);
+        ms.regs.values().max().cloned().ok_or_else(|| Error::Solve(String::from("No registers")))
+    }
+
+    fn part2(program: &Vec<Ins>) -> Result<i32, Error> {
+        Ok(run(program).1)
+    }
+}
@@ -0,0 +1,92 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::Error;
+use aoc::Solution;
+
+#[derive(Copy,Clone,Debug,Default)]
+enum State {
+    #[default]
+    Normal,
+    NormalIgnore,
+    Garbage,
+    GarbageIgnore,
+}
+
+#[derive(Copy,Clone,Default,Debug)]
+pub struct Parser {
+    score: u32,
+    depth: u32,
+    garbage: u32,
+    state: State,
+}
+
+impl Parser {
+    fn consume(&mut self, c: char) -> Option<String> {
+        match self.state {
+            State::Normal => {
+                match c {
+                    '{' => {
+                        self.depth += 1;
+                        self.score += self.depth;
+                    },
+                    '}' => {
+                        if self.depth == 0 {
+                            return Some(String::from("Unbalanced closing brace"));
+                        }
+                        self.depth -= 1;
+                    },
+                    ',' => {},
+                    '!' => {
+                        self.state = State::NormalIgnore;
+                    },
+                    '<' => {
+                        self.state = State::Garbage;
+                    },
+                    _ => return Some(String::from("Unexpected character")),
+                }
+            },
+            State::Garbage => {
+                match c {
+                    '>' => self.state = State::Normal,
+                    '!' => self.state = State::GarbageIgnore,
+                    _ => self.garbage += 1,
+                }
+            },
+            State::NormalIgnore => self.state = State::Normal,
+            State::GarbageIgnore => self.state = State::Garbage,
+        }
+        None
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Parser;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Parser, Error> {
+        let p = aoc::map_line(input, |line| {
+            let line = line.trim();
+            let mut p = Parser::default();
+            for (i, c) in line.char_indices() {
+                if let Some(e) = p.consume(c) {
+                    return Err(Error::token(line, &line[i..i + c.len_utf8()], e));
+                }
+            }
+            Ok(p)
+        })?;
+        debug!("{:?}", p);
+        Ok(p)
+    }
+
+    fn part1(p: &Parser) -> Result<u32, Error> {
+        Ok(p.score)
+    }
+
+    fn part2(p: &Parser) -> Result<u32, Error> {
+        Ok(p.garbage)
+    }
+}
@@ -1,72 +1,9 @@
-use std::io;
-use std::io::BufRead;
+extern crate aoc;
+extern crate star17;
 
-#[derive(Copy,Clone,Debug)]
-enum State {
-    Normal,
-    NormalIgnore,
-    Garbage,
-    GarbageIgnore,
-}
-impl Default for State {
-    fn default() -> State {
-        State::Normal
-    }
-}
-
-#[derive(Copy,Clone,Default,Debug)]
-struct Parser {
-    score: u32,
-    depth: u32,
-    state: State,
-}
-
-impl Parser {
-    fn consume(&mut self, c: char) -> Option<String> {
-        match self.state {
-            State::Normal => {
-                match c {
-                    '{' => {
-                        self.depth += 1;
-                        self.score += self.depth;
-                    },
-                    '}' => {
-                        self.depth -= 1;
-                    },
-                    ',' => {},
-                    '!' => {
-                        self.state = State::NormalIgnore;
-                    },
-                    '<' => {
-                        self.state = State::Garbage;
-                    },
-                    c => return Some(format!("Unexpected char \"{}\"", c)),
-                }
-            },
-            State::Garbage => {
-                match c {
-                    '>' => self.state = State::Normal,
-                    '!' => self.state = State::GarbageIgnore,
-                    c => return Some(format!("Unexpected char \"{}\"", c)),
-                }
-            },
-            State::NormalIgnore => self.state = State::Normal,
-            State::GarbageIgnore => self.state = State::Garbage,
-        }
-        None
-    }
-}
+use aoc::Solution;
+use star17::Day09;
 
 fn main() {
-    let stdin = io::stdin();
-    for line in stdin.lock().lines().map(|l| l.unwrap()) {
-        if line.trim().len() == 0 {
-            continue;
-        }
-        let mut p = Parser::default();
-        for c in line.chars() {
-            p.consume(c);
-        }
-        println!("{:?}", p);
-    }
+    aoc::main(Day09::parse, |input| Day09::part1(input).map(|a| format!("Score: {}", a)));
 }
@@ -0,0 +1,40 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::hash::KnotHasher;
+use aoc::Error;
+use aoc::Solution;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<String, Error> {
+        Ok(input.trim().to_owned())
+    }
+
+    fn part1(input: &String) -> Result<usize, Error> {
+        let mut hasher = KnotHasher::single_round();
+        for s in input.split(',') {
+            hasher.push_length(aoc::parse_token(input, s.trim())?)
+                .map_err(|e| e.within(input, s.trim()))?;
+        }
+        let hash = hasher.sparse()?;
+        for chunk in hash.chunks(32) {
+            debug!("{:?}", chunk);
+        }
+        Ok(hash[0] as usize * hash[1] as usize)
+    }
+
+    fn part2(input: &String) -> Result<String, Error> {
+        let mut hasher = KnotHasher::new();
+        hasher.update(input.as_bytes())?;
+        for chunk in hasher.sparse()?.chunks(32) {
+            debug!("{:?}", chunk);
+        }
+        Ok(hasher.dense()?.to_string())
+    }
+}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::hex;
+use aoc::hex::Hex;
+use aoc::hex::HexDir;
+use aoc::Error;
+use aoc::Solution;
+
+fn walk(steps: &[HexDir]) -> Hex<i64> {
+    steps.iter().fold(Hex::default(), |p, &d| p.step(d))
+}
+
+// The shortest route from the start to where the steps end up
+pub fn route(steps: &[HexDir]) -> Vec<HexDir> {
+    Hex::default().path_to(walk(steps))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<HexDir>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<HexDir>, Error> {
+        aoc::map_line(input, hex::parse_dirs)
+    }
+
+    fn part1(steps: &Vec<HexDir>) -> Result<i64, Error> {
+        let end = walk(steps);
+        debug!("{:?}", end);
+        trace!("Route: {}", hex::format_dirs(&route(steps)));
+        Ok(end.distance())
+    }
+
+    fn part2(steps: &Vec<HexDir>) -> Result<i64, Error> {
+        Ok(steps.iter()
+            .scan(Hex::<i64>::default(), |acc, &d| {
+                *acc = acc.step(d);
+                Some(acc.distance())
+            }).max().unwrap_or(0))
+    }
+}
@@ -0,0 +1,54 @@
+extern crate aoc;
+
+use std::collections::HashSet;
+use aoc::graph::Graph;
+use aoc::Error;
+use aoc::Solution;
+
+fn parse_edges(s: &str) -> Result<(u32, Vec<u32>), Error> {
+    let splitted: Vec<_> = s.split(" <-> ").collect();
+    if splitted.len() != 2 {
+        return Err(Error::token(s, s.trim(), "Expected <node> <-> <nodes>"));
+    }
+    let node = aoc::parse_token(s, splitted[0].trim())?;
+    let mut connected = Vec::new();
+    for other in splitted[1].split(',').map(|o| o.trim()) {
+        connected.push(aoc::parse_token(s, other)?);
+    }
+    Ok((node,connected))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Graph<u32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Graph<u32>, Error> {
+        let edges = aoc::map_lines(input, parse_edges)?;
+        let known: HashSet<_> = edges.iter().map(|&(node, _)| node).collect();
+        let mut graph = Graph::undirected();
+        for &(node, ref connected) in &edges {
+            graph.add_node(node);
+            for &other in connected {
+                if !known.contains(&other) {
+                    return Err(Error::from(format!("Node {} is connected to unknown node {}", node, other)));
+                }
+                graph.add_edge(node, other);
+            }
+        }
+        Ok(graph)
+    }
+
+    fn part1(graph: &Graph<u32>) -> Result<usize, Error> {
+        if !graph.contains(0) {
+            return Err(Error::Solve(String::from("No program 0")));
+        }
+        Ok(graph.reachable(0).len())
+    }
+
+    fn part2(graph: &Graph<u32>) -> Result<usize, Error> {
+        Ok(graph.components().count())
+    }
+}
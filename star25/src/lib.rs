@@ -0,0 +1,117 @@
+#[macro_use]
+extern crate aoc;
+
+use std::str::FromStr;
+use aoc::Error;
+use aoc::Solution;
+
+#[derive(Copy,Clone,Debug,Default,PartialEq,Eq)]
+pub struct Scanner {
+    depth: u32,
+    range: u32,
+}
+
+impl Scanner {
+    fn pos(&self, t: u32) -> u32 {
+        match self.range {
+            0 => u32::MAX,
+            1 => 0,
+            r => {
+                let slot = t % self.period();
+                slot - 2*slot.saturating_sub(r-1)
+            },
+        }
+    }
+    // Time to get back to the top, for a range of at least 2
+    fn period(&self) -> u32 {
+        2 * (self.range - 1)
+    }
+    fn hit(&self, t: u32) -> bool {
+        self.pos(t) == 0
+    }
+    fn hitcost(&self) -> u32 {
+        self.depth * self.range
+    }
+    fn cost(&self, t: u32) -> Option<u32> {
+        match self.hit(t) {
+            true => Some(self.hitcost()),
+            false => None,
+        }
+    }
+    fn new(d:u32, r:u32) -> Scanner {
+        Scanner{depth: d, range: r}
+    }
+}
+
+impl FromStr for Scanner {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self,Self::Err> {
+        let v: Vec<_> = s.split(':').map(|x| x.trim()).collect();
+        if v.len() != 2 {
+            return Err(Error::token(s, s.trim(), "Expected <depth>: <range>"));
+        }
+        let depth = aoc::parse_token(s, v[0])?;
+        let range = aoc::parse_token(s, v[1])?;
+        if range < 1 {
+            return Err(Error::token(s, v[1], "Scanners have a range of at least 1"));
+        }
+        Ok(Scanner::new(depth, range))
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Scanner>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Scanner>, Error> {
+        aoc::parse_lines(input)
+    }
+
+    fn part1(scanners: &Vec<Scanner>) -> Result<u32, Error> {
+        let mut cost = 0u32;
+        for s in scanners {
+            let t = s.depth;
+            if let Some(c) = s.cost(t) {
+                cost += c;
+            }
+            trace!("T:{:3}, Pos:{:3}, Acc:{:4}, Cost:{:?}", t, s.pos(t), cost, s.cost(t));
+        }
+        Ok(cost)
+    }
+
+    fn part2(scanners: &Vec<Scanner>) -> Result<u32, Error> {
+        if let Some(s) = scanners.iter().find(|s| s.range == 1) {
+            return Err(Error::Solve(format!("The scanner at depth {} catches every packet", s.depth)));
+        }
+        // All scanners are back where they started after the lcm of their
+        // periods, so if no delay below it gets through none does
+        let cycle = scanners.iter()
+            .filter(|s| s.range > 1)
+            .map(|s| u64::from(s.period()))
+            .fold(1, |l, p| (l / gcd(l, p) * p).min(1 << 32));
+        for delay in 0..cycle {
+            let delay = delay as u32;
+            let mut cost = 0u32;
+            let mut caught = false;
+            for s in scanners {
+                let t = s.depth + delay;
+                if let Some(c) = s.cost(t) {
+                    cost += c;
+                    caught = true;
+                }
+            }
+            if cost == 0 && !caught {
+                debug!("d:{:8} cost:{:5} {}", delay, cost, caught);
+                return Ok(delay);
+            }
+        }
+        Err(Error::Solve(String::from("Every delay gets caught")))
+    }
+}
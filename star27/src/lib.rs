@@ -0,0 +1,97 @@
+#[macro_use]
+extern crate aoc;
+use aoc::hash::Knot;
+use aoc::graph::Graph;
+use aoc::grid::Grid;
+use aoc::par;
+use aoc::Error;
+use aoc::Solution;
+
+fn row_hash(key: &str, line: usize) -> Knot {
+    Knot::hash(format!("{}-{}", key, line).as_bytes())
+}
+
+// The hashes of all rows, spread over the given number of threads
+pub fn row_hashes(key: &str, threads: usize) -> Vec<Knot> {
+    let rows: Vec<usize> = (0..128).collect();
+    par::map(&rows, threads, |&line| row_hash(key, line))
+}
+
+// The used squares of the disk
+pub fn grid_with(key: &str, threads: usize) -> Result<Grid<bool>, Error> {
+    let rows = row_hashes(key, threads).iter().map(|k| k.bits().collect()).collect();
+    Grid::from_rows(rows)
+}
+
+pub fn grid(key: &str) -> Result<Grid<bool>, Error> {
+    grid_with(key, par::threads())
+}
+
+fn build_graph(grid: &Grid<bool>) -> Graph<(usize, usize)> {
+    let mut g = Graph::undirected();
+
+    for (pos, &used) in grid.positions() {
+        if !used {
+            continue;
+        }
+        g.add_node(pos);
+        for neigh in grid.neighbours4(pos).filter(|&n| grid[n]) {
+            g.add_edge(pos, neigh);
+        }
+    }
+
+    g
+}
+
+// The region of every used square, numbered from 0 in reading order
+pub fn regions(grid: &Grid<bool>) -> Grid<Option<usize>> {
+    let components = build_graph(grid).components();
+    grid.map(|p, _| components.label(p))
+}
+
+// The region numbers as text, free squares as dots
+pub fn format_regions(regions: &Grid<Option<usize>>) -> String {
+    let max = regions.iter().filter_map(|&r| r).max().unwrap_or(0);
+    let width = max.to_string().len();
+    let mut s = String::new();
+    for row in regions.rows() {
+        let cells: Vec<_> = row.iter()
+            .map(|r| match *r {
+                Some(r) => format!("{:>1$}", r, width),
+                None => format!("{:>1$}", ".", width),
+            })
+            .collect();
+        s.push_str(cells.join(" ").trim_end());
+        s.push('\n');
+    }
+    s
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = String;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<String, Error> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(key: &String) -> Result<u32, Error> {
+        let mut sum = 0u32;
+        for (line, k) in row_hashes(key, par::threads()).iter().enumerate() {
+            let cnt = k.count_ones();
+            trace!("{} {}-{} {}", k, key, line, cnt);
+            sum += cnt;
+        }
+        Ok(sum)
+    }
+
+    fn part2(key: &String) -> Result<usize, Error> {
+        let g = grid(key)?;
+        debug!("{}", g.to_string().trim_end());
+        let graph = build_graph(&g);
+        Ok(graph.components().count())
+    }
+}
@@ -1,24 +1,9 @@
 extern crate aoc;
-use std::io;
-use aoc::input::lines;
-use aoc::hash::Knot;
-use std::str::FromStr;
+extern crate star27;
 
-fn popcnt(k: &Knot) -> u32 {
-    k.iter().fold(0, |sum, c| sum + c.count_ones())
-}
+use aoc::Solution;
+use star27::Day14;
 
 fn main() {
-    let stdin = io::stdin();
-    for key in lines(&stdin) {
-        let mut sum = 0u32;
-        for line in 0..128 {
-            let line_key = format!("{}-{}", key, line);
-            let k = Knot::from_str(&line_key).unwrap();
-            let cnt = popcnt(&k);
-            println!("{} {} {}", k, line_key, cnt);
-            sum += cnt;
-        }
-        println!("Number of bits: {}", sum);
-    }
+    aoc::main(Day14::parse, |input| Day14::part1(input).map(|a| format!("Number of bits: {}", a)));
 }
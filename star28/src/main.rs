@@ -1,100 +1,122 @@
 extern crate aoc;
-use std::io;
-use aoc::input::lines;
-use aoc::hash::Knot;
-use aoc::graph::Implicit;
+extern crate star27;
 
-use std::str::FromStr;
+use std::env;
+use std::fs;
+use std::process;
+use aoc::bench;
+use aoc::image;
+use aoc::log;
+use aoc::par;
+use aoc::Solution;
+use star27::Day14;
 
-fn popcnt(k: &Knot) -> u32 {
-    k.iter().fold(0, |sum, c| sum + c.count_ones())
+fn usage() -> ! {
+    eprintln!("Usage: star28 [-v...] [--image <file.pbm|file.pgm|file.png>] [--scale <n>] [--regions] [--labels]");
+    eprintln!("       star28 [-v...] --bench [--runs <n>]");
+    eprintln!();
+    eprintln!("Reads the key from stdin. --image draws the disk, with --regions every");
+    eprintln!("region of a PGM or PNG gets its own shade or colour. --labels prints the");
+    eprintln!("region numbers as text, they aren't drawn.");
+    eprintln!("--bench times building the disk on one thread against all of them, set");
+    eprintln!("{} to change the number of threads.", par::THREADS_VAR);
+    process::exit(2);
 }
 
-fn explode_u8(u: u8) -> [bool; 8] {
-    let mut ret = [false; 8];
-    for bit in 0..8 {
-        let bitmask = 0x80 >> bit;
-        ret[bit] = u & bitmask > 0
-    }
-    ret
-}
-fn explode(k: &Knot) -> Vec<bool> {
-    let mut ret = Vec::with_capacity(128);
-    for c in k.iter() {
-        let bits = explode_u8(*c);
-        ret.extend(&bits);
-    }
-    ret
-}
-fn grid(key: &str) -> Vec<bool> {
-    let mut ret = Vec::new();
-    for line in 0..128 {
-        let line_key = format!("{}-{}", key, line);
-        let k = Knot::from_str(&line_key).unwrap();
-        ret.extend(explode(&k));
-    }
-    ret
-}
-
-fn clamp(x: isize) -> Option<usize> {
-    if x >= 0 && x < 128 {
-        Some(x as usize)
+// Draws the disk in the format the file name asks for
+fn draw(key: &str, path: &str, scale: usize, regions: bool) -> Result<(), String> {
+    let grid = star27::grid(key).map_err(|e| e.to_string())?;
+    let labels = star27::regions(&grid);
+    let data = if path.ends_with(".pbm") {
+        image::pbm(&grid.scale(scale))
+    } else if path.ends_with(".pgm") {
+        let grey = labels.map(|_, r| match *r {
+            Some(r) if regions => image::grey(r),
+            Some(_) => 0,
+            None => 255,
+        });
+        image::pgm(&grey.scale(scale))
+    } else if path.ends_with(".png") {
+        let rgb = labels.map(|_, r| match *r {
+            Some(r) if regions => image::colour(r),
+            Some(_) => image::BLACK,
+            None => image::WHITE,
+        });
+        image::png(&rgb.scale(scale))
     } else {
-        None
-    }
+        return Err(format!("Unknown image format of {}, use .pbm, .pgm or .png", path));
+    };
+    fs::write(path, data).map_err(|e| format!("Can't write {}: {}", path, e))
 }
 
-fn map_grid_id(grid: &Vec<bool>, x: isize, y: isize) -> Option<usize> {
-    let idx = clamp(y)? * 128 + clamp(x)?;
-    if grid[idx] {
-        Some(idx)
-    } else {
-        None
+// Times the row hashes on a single thread and in parallel
+fn bench(key: &str, runs: usize) -> Result<(), String> {
+    let threads = par::threads();
+    let sequential = star27::grid_with(key, 1).map_err(|e| e.to_string())?;
+    let parallel = star27::grid_with(key, threads).map_err(|e| e.to_string())?;
+    if sequential != parallel {
+        return Err(String::from("The parallel grid differs from the sequential one"));
     }
-}
-
-fn neighbours(grid: &Vec<bool>, id: usize) -> [Option<usize>;4] {
-    let mut ret = [None; 4];
-    let x = (id % 128) as isize;
-    let y = (id / 128) as isize;
-
-    ret[0] = map_grid_id(grid, x + 1, y); 
-    ret[1] = map_grid_id(grid, x - 1, y); 
-    ret[2] = map_grid_id(grid, x, y + 1); 
-    ret[3] = map_grid_id(grid, x, y - 1); 
 
-    ret
+    println!("{:>7} {:>5} {:>9} {:>9} {:>9} {:>8}", "threads", "runs", "min", "median", "max", "change");
+    let mut base = None;
+    let counts = if threads > 1 { vec![1, threads] } else { vec![1] };
+    for &n in &counts {
+        let stats = bench::measure(runs, || star27::grid_with(key, n)).map_err(|e| e.to_string())?;
+        let base = *base.get_or_insert(stats.median);
+        println!("{:>7} {:>5} {:>9} {:>9} {:>9} {:>8}", n, stats.runs, bench::human(stats.min),
+                 bench::human(stats.median), bench::human(stats.max), bench::change(base, stats.median));
+    }
+    Ok(())
 }
 
-fn build_graph(grid: &Vec<bool>) -> Implicit<usize> {
-    let mut g = Implicit::new();
+fn main() {
+    log::init();
+    let mut image = None;
+    let mut scale = 1;
+    let mut regions = false;
+    let mut labels = false;
+    let mut benchmark = false;
+    let mut runs = 10;
 
-    for pos in 0..grid.len() {
-        if !grid[pos] {
-            continue;
-        }
-        g.insert(pos, None);
-        for neigh in &neighbours(grid, pos) {
-            g.insert(pos, *neigh);
+    let args: Vec<String> = env::args().skip(1).filter(|a| log::flag(a).is_none()).collect();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--image" => image = Some(args.next().unwrap_or_else(|| usage())),
+            "--scale" => scale = args.next().and_then(|n| n.parse().ok()).filter(|&n| n > 0)
+                .unwrap_or_else(|| usage()),
+            "--regions" => regions = true,
+            "--labels" => labels = true,
+            "--bench" => benchmark = true,
+            "--runs" => runs = args.next().and_then(|n| n.parse().ok()).unwrap_or_else(|| usage()),
+            _ => usage(),
         }
     }
+    // A bitmap only has black and white, so it can't tell regions apart
+    if regions && !image.as_ref().is_some_and(|p| p.ends_with(".pgm") || p.ends_with(".png")) {
+        eprintln!("--regions needs an --image ending in .pgm or .png");
+        process::exit(2);
+    }
 
-    g
-}
-
-fn printgrid(grid: &Vec<bool>) {
-    for chunk in grid.chunks(128) {
-        let s: String = chunk.iter().map(|&b| if b { '#' } else { '.' }).collect();
-        println!("{}", s);
+    let input = aoc::or_exit(aoc::parse_stdin(Day14::parse));
+    if benchmark {
+        if let Err(e) = bench(&input, runs) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        return;
     }
-}
+    println!("Number of regions: {}", aoc::or_exit(Day14::part2(&input)));
 
-fn main() {
-    let stdin = io::stdin();
-    for key in lines(&stdin) {
-        let g = grid(&key);
-        printgrid(&g);
-        let graph = build_graph(&g);
-        println!("Number of regions: {}", graph.num_groups());
+    if labels {
+        let grid = aoc::or_exit(star27::grid(&input));
+        print!("{}", star27::format_regions(&star27::regions(&grid)));
+    }
+    if let Some(path) = image {
+        if let Err(e) = draw(&input, &path, scale, regions) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
     }
 }
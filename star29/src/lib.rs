@@ -0,0 +1,78 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::Error;
+use aoc::Solution;
+
+#[derive(Copy,Clone,Debug)]
+struct Generator {
+    state: u64,
+    fac: u64,
+    mask: u64,
+}
+
+impl Iterator for Generator {
+    type Item = u64;
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            self.state *= self.fac;
+            self.state %= 2147483647;
+            if self.state & self.mask == 0 {
+                break
+            }
+        }
+        Some(self.state)
+    }
+}
+
+impl Generator {
+    fn new(state: u64, fac: u64, mask: u64) -> Generator {
+        Generator{state, fac, mask}
+    }
+}
+
+fn judge(gen_a: Generator, gen_b: Generator, rounds: usize) -> u64 {
+    let mut count = 0u64;
+    for (s_a, s_b) in gen_a.zip(gen_b).take(rounds) {
+        if s_a & 0xffff != s_b & 0xffff {
+            continue;
+        }
+
+        count += 1;
+        trace!("{:6}: {:10} == {:10}", count, s_a, s_b);
+    }
+
+    count
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (u64, u64);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<(u64, u64), Error> {
+        let inputs = aoc::map_lines(input, |l| {
+            let start = l.split_whitespace().last().ok_or("Missing start value")?;
+            aoc::parse_token(l, start)
+        })?;
+
+        if inputs.len() != 2 {
+            return Err(Error::from("Expected two generators"));
+        }
+        Ok((inputs[0], inputs[1]))
+    }
+
+    fn part1(&(a, b): &(u64, u64)) -> Result<u64, Error> {
+        let gen_a = Generator::new(a, 16807, 0);
+        let gen_b = Generator::new(b, 48271, 0);
+        Ok(judge(gen_a, gen_b, 40_000_000))
+    }
+
+    fn part2(&(a, b): &(u64, u64)) -> Result<u64, Error> {
+        let gen_a = Generator::new(a, 16807, 4-1);
+        let gen_b = Generator::new(b, 48271, 8-1);
+        Ok(judge(gen_a, gen_b, 5_000_000))
+    }
+}
@@ -0,0 +1,148 @@
+extern crate aoc;
+
+use std::str::FromStr;
+use aoc::Error;
+use aoc::Solution;
+
+#[derive(Copy,Clone,Debug,PartialEq,Eq)]
+pub enum Move {
+    Spin(usize),
+    Exchange(usize, usize),
+    Partner(u8, u8),
+}
+
+fn rotate(v: Vec<u8>, r: usize) -> Vec<u8> {
+    let l = v.len();
+    v.into_iter().cycle().skip(l-r).take(l).collect()
+}
+
+fn pair<'a>(m: &str, s: &'a str) -> Result<(&'a str, &'a str), Error> {
+    let mut pos = s.split('/');
+    match (pos.next(), pos.next(), pos.next()) {
+        (Some(x), Some(y), None) => Ok((x, y)),
+        _ => Err(Error::token(m, s, "Expected a pair separated by \"/\"")),
+    }
+}
+
+fn name(m: &str, s: &str) -> Result<u8, Error> {
+    match *s.as_bytes() {
+        [c] if c.is_ascii_lowercase() => Ok(c),
+        _ => Err(Error::token(m, s, "Invalid program name")),
+    }
+}
+
+impl FromStr for Move {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kind = s.chars().next().ok_or("Empty move")?;
+        let (kind, args) = s.split_at(kind.len_utf8());
+        match kind {
+            "s" => Ok(Move::Spin(aoc::parse_token(s, args)?)),
+            "x" => {
+                let (x,y) = pair(s, args)?;
+                Ok(Move::Exchange(aoc::parse_token(s, x)?, aoc::parse_token(s, y)?))
+            },
+            "p" => {
+                let (x,y) = pair(s, args)?;
+                Ok(Move::Partner(name(s, x)?, name(s, y)?))
+            },
+            e => Err(Error::token(s, e, "Unknown move")),
+        }
+    }
+}
+
+fn do_moves(v: &str, moves: &[Move]) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let from = Vec::from(v);
+    let mut positional_swaps = from.clone();
+    let mut renames = from.clone();
+
+    for dance_move in moves {
+        match *dance_move {
+            Move::Spin(shift) => {
+                positional_swaps = rotate(positional_swaps, shift % from.len());
+            },
+            Move::Exchange(x,y) => {
+                if x >= from.len() || y >= from.len() {
+                    return Err(Error::Solve(format!("Can't exchange {} and {}", x, y)));
+                }
+                positional_swaps.swap(x,y);
+            },
+            Move::Partner(xname,yname) => {
+                let x = position(&renames, xname)?;
+                let y = position(&renames, yname)?;
+                renames.swap(x,y);
+            },
+        }
+    }
+    Ok((compile(&from, &positional_swaps), renames))
+}
+
+fn position(v: &[u8], name: u8) -> Result<usize, Error> {
+    v.iter().position(|&x| x == name)
+        .ok_or_else(|| Error::Solve(format!("No program named {}", name as char)))
+}
+
+fn compile(from: &[u8], to: &[u8]) -> Vec<u8> {
+    let mut ret = Vec::new();
+    for c in to {
+       let y = from.iter().position(|y| y == c).unwrap();
+       ret.push(y as u8);
+    }
+    ret
+}
+
+fn do_swaps(state: &[u8], program: &[u8]) -> Vec<u8>{
+    program.iter().map(|&pos| state[pos as usize]).collect()
+}
+
+fn do_renames(state: &[u8], program: &[u8]) -> Vec<u8>{
+    state.iter().map(|&c| program[(c as usize) - ('a' as usize)]).collect()
+}
+
+fn dance(moves: &[Move], rounds: u64) -> Result<String, Error> {
+    let from = String::from("abcdefghijklmnop");
+
+    let (mut swaps, mut renames) = do_moves(&from, moves)?;
+
+    let mut t = from.into_bytes();
+    let mut rounds = rounds;
+
+    while rounds > 0 {
+        if rounds & 1 > 0 {
+            t = do_swaps(&t, &swaps);
+            t = do_renames(&t, &renames);
+        }
+
+        swaps = do_swaps(&swaps, &swaps);
+        renames = do_renames(&renames, &renames);
+
+        rounds >>= 1;
+    }
+
+    Ok(String::from_utf8(t).unwrap())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Vec<Move>;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Move>, Error> {
+        aoc::map_line(input, |line| {
+            line.split(',')
+                .map(|m| m.trim())
+                .map(|m| m.parse().map_err(|e: Error| e.within(line, m)))
+                .collect()
+        })
+    }
+
+    fn part1(moves: &Vec<Move>) -> Result<String, Error> {
+        dance(moves, 1)
+    }
+
+    fn part2(moves: &Vec<Move>) -> Result<String, Error> {
+        dance(moves, 1_000_000_000)
+    }
+}
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::Error;
+use aoc::Solution;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = usize;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<usize, Error> {
+        aoc::map_line(input, |line| aoc::parse_token(line, line.trim()))
+    }
+
+    fn part1(&steps: &usize) -> Result<usize, Error> {
+        let mut v = vec![0];
+        let mut pos = 0;
+
+        for val in 1..2018 {
+            pos = (pos + 1 + steps) % v.len();
+            v.insert(pos, val);
+            trace!("{} {:?}", val, v);
+        }
+
+        Ok(v[(pos+1) % v.len()])
+    }
+
+    fn part2(&steps: &usize) -> Result<usize, Error> {
+        let mut pos1 = 0;
+        let mut pos = 0;
+
+        for (len, val) in (1..50_000_001).enumerate() {
+            pos = 1 + (pos + steps) % (len + 1);
+            if pos == 1 {
+                pos1 = val;
+            }
+        }
+
+        Ok(pos1)
+    }
+}
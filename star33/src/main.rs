@@ -1,22 +1,9 @@
 extern crate aoc;
-use aoc:: input;
-use std::io;
+extern crate star33;
 
-fn main() {
-    let stdin = io::stdin();
-
-    for line in input::lines(&stdin) {
-        let steps = line.parse::<usize>().unwrap();
-
-        let mut v = vec![0];
-        let mut pos = 0;
+use aoc::Solution;
+use star33::Day17;
 
-        for val in 1..2018 {
-            pos = (pos + 1 + steps) % v.len();
-            v.insert(pos, val);
-            println!("{} {:?}", val, v);
-        }
-
-        println!("{}", v[(pos+1) % v.len()]);
-    }
+fn main() {
+    aoc::main(Day17::parse, |input| Day17::part1(input).map(|a| format!("Value after 2017: {}", a)));
 }
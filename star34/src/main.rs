@@ -1,25 +1,9 @@
 extern crate aoc;
-use aoc:: input;
-use std::io;
+extern crate star33;
 
-fn main() {
-    let stdin = io::stdin();
-
-    for line in input::lines(&stdin) {
-        let steps = line.parse::<usize>().unwrap();
-
-        let mut len = 1;
-        let mut pos1 = 0;
-        let mut pos = 0;
+use aoc::Solution;
+use star33::Day17;
 
-        for val in 1..50_000_001 {
-            pos = 1 + (pos + steps) % len;
-            len += 1;
-            if pos == 1 {
-                pos1 = val;
-            }
-        }
-
-        println!("{}", pos1)
-    }
+fn main() {
+    aoc::main(Day17::parse, |input| Day17::part2(input).map(|a| format!("Value after 0: {}", a)));
 }
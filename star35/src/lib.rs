@@ -0,0 +1,54 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::Error;
+use aoc::Solution;
+use aoc::duet::Instruction;
+use aoc::duet::InstructionSet;
+use aoc::duet::Mailbox;
+use aoc::duet::Sound;
+use aoc::duet::Vm;
+
+// The sound card interpretation of part 1
+fn sound(prog: &[Instruction]) -> Result<i64, Error> {
+    let mut vm = Vm::new(&InstructionSet::duet(), prog, Sound::new());
+    vm.run()?;
+    vm.io().recovered().ok_or_else(|| Error::Solve(String::from("Nothing was recovered")))
+}
+
+// Two programs sending messages to each other, each with its id in p
+fn duet(prog: &[Instruction]) -> Result<usize, Error> {
+    let set = InstructionSet::duet();
+    let p = set.register("p").unwrap();
+    let mut vms = [Vm::new(&set, prog, Mailbox::new()), Vm::new(&set, prog, Mailbox::new())];
+    for (id, vm) in vms.iter_mut().enumerate() {
+        vm.set_reg(p, id as i64);
+    }
+
+    aoc::duet::run_pair(&mut vms)?;
+
+    for (id, vm) in vms.iter().enumerate() {
+        info!("Vm {} sent {} messages", id, vm.io().sent());
+    }
+    Ok(vms[1].io().sent())
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Instruction>;
+    type Part1 = i64;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, Error> {
+        InstructionSet::duet().parse(input)
+    }
+
+    fn part1(prog: &Vec<Instruction>) -> Result<i64, Error> {
+        sound(prog)
+    }
+
+    fn part2(prog: &Vec<Instruction>) -> Result<usize, Error> {
+        duet(prog)
+    }
+}
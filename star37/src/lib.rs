@@ -0,0 +1,110 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::geom::Vec2;
+use aoc::grid::Grid;
+use aoc::Error;
+use aoc::Solution;
+
+pub struct Pipes {
+    grid: Grid<u8>,
+}
+
+impl Pipes {
+    fn new(data: &str) -> Result<Pipes, Error> {
+        let grid = Grid::parse_with(data, |c| if c.is_ascii() { Some(c as u8) } else { None })?;
+        Ok(Pipes{grid})
+    }
+
+    fn get(&self, v: V2) -> Option<u8> {
+        self.grid.at(v.y, v.x).cloned()
+    }
+
+    fn start(&self) -> Option<V2> {
+        let x = self.grid.row(0)?.iter().position(|&c| c == b'|')?;
+        Some(V2::new(0,x as isize))
+    }
+}
+
+type V2 = Vec2<isize>;
+
+fn is_alpha(c: u8) -> bool {
+    c.is_ascii_alphabetic()
+}
+
+#[derive(Debug)]
+struct Packet {
+    pos: V2,
+    dir: V2,
+}
+
+impl Packet {
+    fn new(pos: V2, dir: V2) -> Packet {
+        Packet{pos, dir}
+    }
+
+    fn step(&self, pipes: &Pipes) -> Option<Packet> {
+        let dir = match pipes.get(self.pos)? {
+            b'-' | b'|' => Some(self.dir),
+            b'+' => {
+                if Packet::new(self.pos + self.dir.rl(), self.dir.rl()).step(pipes).is_some() {
+                    Some(self.dir.rl())
+                } else if Packet::new(self.pos + self.dir.rr(), self.dir.rr()).step(pipes).is_some() {
+                    Some(self.dir.rr())
+                } else {
+                    None
+                }
+            }
+            c if is_alpha(c) => {
+                trace!("Passing {}", c as char);
+                Some(self.dir)
+            },
+            _ => {
+                None
+            },
+        }?;
+        Some(Packet::new(self.pos + dir, dir))
+    }
+}
+
+// Follow the path and return the letters seen on the way and the number of
+// steps taken
+fn walk(p: &Pipes) -> Result<(String, usize), Error> {
+    let start = p.start().ok_or("No entry point found")?;
+    let mut packet = Packet::new(start, V2::new(1,0));
+    let mut letters = String::new();
+    let mut n = 0usize;
+
+    debug!("{}", p.grid.render(|&c| c as char).trim_end());
+    while let Some(next) = packet.step(p) {
+        if let Some(c) = p.get(packet.pos) {
+            if is_alpha(c) {
+                letters.push(c as char);
+            }
+        }
+        packet = next;
+        n += 1;
+    }
+
+    Ok((letters, n))
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Pipes;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Pipes, Error> {
+        Pipes::new(input)
+    }
+
+    fn part1(p: &Pipes) -> Result<String, Error> {
+        Ok(walk(p)?.0)
+    }
+
+    fn part2(p: &Pipes) -> Result<usize, Error> {
+        Ok(walk(p)?.1)
+    }
+}
@@ -0,0 +1,120 @@
+#[macro_use]
+extern crate aoc;
+
+use std::cmp::Ordering;
+use std::str::FromStr;
+use aoc::geom::Vec3;
+use aoc::Error;
+use aoc::Solution;
+
+type V3 = Vec3<i64>;
+
+#[derive(Copy,Clone,Eq,PartialEq,Default,Debug)]
+pub struct Particle {
+    pos: V3,
+    vel: V3,
+    acc: V3,
+}
+
+impl Particle {
+    fn new(pos: V3, vel: V3, acc: V3) -> Particle {
+        Particle{pos,vel,acc}
+    }
+
+    fn step(&self) -> Particle {
+        let vel = self.vel + self.acc;
+        let pos = self.pos + vel;
+        Particle::new(pos,vel,self.acc)
+    }
+}
+
+impl PartialOrd for Particle {
+    fn partial_cmp(&self, other: &Particle) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Particle {
+    fn cmp(&self, other: &Particle) -> Ordering {
+        // Closest in the long run: slowest acceleration, then speed
+        self.acc.manhattan().cmp(&other.acc.manhattan())
+            .then(self.vel.manhattan().cmp(&other.vel.manhattan()))
+            .then(self.pos.manhattan().cmp(&other.pos.manhattan()))
+    }
+}
+
+impl FromStr for Particle {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let v = s.split(", ")
+            .map(|n| match n.find('=') {
+                Some(i) => n[i+1..].parse::<V3>().map_err(|e| e.within(s, &n[i+1..])),
+                None => Err(Error::token(s, n, "Expected <name>=<vector>")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if v.len() != 3 {
+            Err(Error::token(s, s.trim(), "Expected p=<...>, v=<...>, a=<...>"))
+        } else {
+            Ok(Particle::new(v[0],v[1],v[2]))
+        }
+    }
+}
+
+fn step(particles: &mut [Particle]) {
+    for p in particles.iter_mut() {
+        *p = p.step();
+    }
+}
+
+fn remove_collitions(particles: &[Particle]) -> (Vec<Particle>, Vec<Particle>) {
+    particles.iter().partition(|&p1| particles.iter().filter(|&p2| p1.pos == p2.pos).count() == 1)
+}
+
+fn dist_matrix(particles: &[Particle]) -> Vec<i64> {
+    let mut ret = Vec::with_capacity(particles.len()*particles.len());
+    for (i,p) in particles.iter().enumerate() {
+        ret.extend(particles.iter().skip(i+1).map(|p2| (p.pos - p2.pos).manhattan()))
+    }
+    ret
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<Particle>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Particle>, Error> {
+        aoc::parse_lines(input)
+    }
+
+    fn part1(particles: &Vec<Particle>) -> Result<usize, Error> {
+        let min = particles.iter().min().ok_or("No particles")?;
+        let pos = particles.iter().position(|p| p == min).unwrap();
+        trace!("{:?}", particles);
+        debug!("{:?}: {:?}", min, pos);
+        Ok(pos)
+    }
+
+    fn part2(particles: &Vec<Particle>) -> Result<usize, Error> {
+        let mut particles = particles.clone();
+        let mut last_dists = dist_matrix(&particles);
+
+        loop {
+            debug!("Particles left: {}", particles.len());
+            step(&mut particles);
+            let distance = dist_matrix(&particles);
+            if last_dists.len() == distance.len() && distance.iter().ge(last_dists.iter()) {
+                break;
+            }
+            let (saved, removed) = remove_collitions(&particles);
+            last_dists = distance;
+            particles = saved;
+            trace!("{:?} {:?}", removed, last_dists.iter().min());
+
+        }
+
+        Ok(particles.len())
+    }
+}
@@ -0,0 +1,104 @@
+#[macro_use]
+extern crate aoc;
+
+use std::collections::BTreeMap;
+use aoc::grid;
+use aoc::grid::Grid;
+use aoc::Error;
+use aoc::Solution;
+
+fn parse_grid(input: &str) -> Result<Grid<bool>, Error> {
+    let grid = Grid::parse_rows(input, input.split('/'), grid::parse_cell)?;
+    if grid.height() != grid.width() {
+        return Err(Error::token(input, input, "Grid is not a square"));
+    }
+    Ok(grid)
+}
+
+fn parse_rule(input: &str) -> Result<(Grid<bool>, Grid<bool>), Error> {
+    let split: Vec<_> = input.split(" => ").map(|s| s.trim()).collect();
+    if split.len() != 2 {
+        return Err(Error::token(input, input.trim(), "Expected <pattern> => <pattern>"));
+    }
+    let from = parse_grid(split[0]).map_err(|e| e.within(input, split[0]))?;
+    let to = parse_grid(split[1]).map_err(|e| e.within(input, split[1]))?;
+    Ok((from, to))
+}
+
+// The same orientation for every rotation and mirror image of a pattern
+fn canonical(g: &Grid<bool>) -> Grid<bool> {
+    g.orientations().into_iter().max().unwrap()
+}
+
+fn enhance_grid(g: &Grid<bool>, rules: &BTreeMap<Grid<bool>, Grid<bool>>) -> Result<Grid<bool>, &'static str> {
+    let size = g.width();
+    let chunksize = if size.is_multiple_of(2) {
+        2
+    } else if size.is_multiple_of(3) {
+        3
+    } else {
+        return Err("Grid size is neither divisible by 2 nor 3");
+    };
+
+    let steps = size / chunksize;
+    let newchunksize = chunksize + 1;
+    let newsize = newchunksize * steps;
+
+    let mut ret = Grid::new(newsize, newsize, false);
+
+    for offy in 0..steps {
+        for offx in 0..steps {
+            let src = g.sub_grid((offy * chunksize, offx * chunksize), chunksize, chunksize).unwrap();
+            let enhanced = rules.get(&canonical(&src)).ok_or("No matching rule")?;
+            if enhanced.width() != newchunksize {
+                return Err("Rule produces the wrong size");
+            }
+            ret.splice((offy * newchunksize, offx * newchunksize), enhanced).unwrap();
+        }
+    }
+
+    Ok(ret)
+}
+
+fn enhance(ruleset: &BTreeMap<Grid<bool>, Grid<bool>>, iterations: usize) -> Result<usize, Error> {
+    let mut g = parse_grid(".#./..#/###")?;
+
+    for i in 0..iterations {
+        g = enhance_grid(&g, ruleset)?;
+        debug!("Step {}", i);
+        trace!("{}", g);
+    }
+
+    Ok(g.iter().filter(|&&c| c).count())
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = BTreeMap<Grid<bool>, Grid<bool>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<BTreeMap<Grid<bool>, Grid<bool>>, Error> {
+        let rules = aoc::map_lines(input, parse_rule)?;
+
+        let mut ruleset = BTreeMap::new();
+
+        for (from, to) in rules {
+            trace!("{}\n->\n{}", from, to);
+            trace!("-----------------------");
+
+            ruleset.insert(canonical(&from), to);
+        }
+
+        Ok(ruleset)
+    }
+
+    fn part1(ruleset: &BTreeMap<Grid<bool>, Grid<bool>>) -> Result<usize, Error> {
+        enhance(ruleset, 5)
+    }
+
+    fn part2(ruleset: &BTreeMap<Grid<bool>, Grid<bool>>) -> Result<usize, Error> {
+        enhance(ruleset, 18)
+    }
+}
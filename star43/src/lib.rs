@@ -0,0 +1,162 @@
+#[macro_use]
+extern crate aoc;
+
+use std::fmt;
+use aoc::geom::Dir4;
+use aoc::geom::Vec2;
+use aoc::grid::Grid;
+use aoc::grid::Sparse;
+use aoc::Error;
+use aoc::Solution;
+
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+enum NodeState {
+    #[default]
+    Clean,
+    Weakened,
+    Infected,
+    Flagged,
+}
+
+impl fmt::Display for NodeState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match *self {
+            NodeState::Clean    => '.',
+            NodeState::Weakened => 'W',
+            NodeState::Infected => '#',
+            NodeState::Flagged  => 'F',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+impl From<bool> for NodeState {
+    fn from(infected: bool) -> Self {
+        if infected {
+            NodeState::Infected
+        } else {
+            NodeState::Clean
+        }
+    }
+}
+
+impl NodeState {
+    fn visit(&self) -> NodeState {
+        match *self {
+            NodeState::Clean    => NodeState::Weakened,
+            NodeState::Weakened => NodeState::Infected,
+            NodeState::Infected => NodeState::Flagged,
+            NodeState::Flagged  => NodeState::Clean,
+        }
+    }
+}
+
+// The initial grid is centered on the origin
+fn infinite<T: Clone>(init: &Grid<T>, clean: T) -> Sparse<T> {
+    let origin = (-(init.height() as isize / 2), -(init.width() as isize / 2));
+    Sparse::from_grid(init, origin, clean)
+}
+
+fn format_grid(g: &Sparse<bool>) -> String {
+    g.to_grid().map(|g| g.to_string()).unwrap_or_default()
+}
+
+#[derive(Copy,Clone,Eq,PartialEq,Debug)]
+struct Carrier {
+    pos: Vec2<isize>,
+    dir: Dir4,
+}
+
+impl Default for Carrier {
+    fn default() -> Self {
+        Carrier {
+            pos: Vec2::default(),
+            dir: Dir4::Up,
+        }
+    }
+}
+
+impl Carrier {
+    fn mv(&self) -> Carrier {
+        Carrier {
+            pos: self.pos + self.dir.vec(),
+            dir: self.dir,
+        }
+    }
+    fn turn(&self, state: &NodeState) -> Carrier {
+        let dir = match *state {
+            NodeState::Clean    => self.dir.rl(),
+            NodeState::Weakened => self.dir,
+            NodeState::Infected => self.dir.rr(),
+            NodeState::Flagged  => self.dir.reverse(),
+        };
+        Carrier {
+            pos: self.pos,
+            dir,
+        }
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Grid<bool>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Grid<bool>, Error> {
+        let init = Grid::parse(input)?;
+        if !init.iter().any(|&c| c) {
+            return Err(Error::from("No infected nodes"));
+        }
+        Ok(init)
+    }
+
+    fn part1(init: &Grid<bool>) -> Result<usize, Error> {
+        let iterations = 10_000;
+        let mut infections = 0usize;
+        let mut grid = infinite(init, false);
+        let mut carrier = Carrier::default();
+        trace!("\x1B[2J");
+        for _ in 0..iterations {
+            trace!("\x1B[H{}Number of infections: {}", format_grid(&grid), infections);
+
+            let node = grid.get_mut(carrier.pos.into());
+            let infected = *node;
+            if !infected {
+                infections += 1;
+            }
+            carrier = carrier.turn(&NodeState::from(infected));
+            *node = !infected;
+            carrier = carrier.mv();
+        }
+
+        debug!("{}", format_grid(&grid).trim_end());
+
+        Ok(infections)
+    }
+
+    fn part2(init: &Grid<bool>) -> Result<usize, Error> {
+        let iterations = 10_000_000;
+        let mut infections = 0usize;
+        let mut grid = infinite(&init.map(|_, &c| NodeState::from(c)), NodeState::Clean);
+        let mut carrier = Carrier::default();
+        for i in 0..iterations {
+            if i % 1_000_000 == 0 {
+                debug!("Number of infections: {} {:?}", infections, grid.bounds());
+            }
+
+            let node = grid.get_mut(carrier.pos.into());
+            let state = *node;
+            let next_state = state.visit();
+            if next_state == NodeState::Infected {
+                infections += 1;
+            }
+            carrier = carrier.turn(&state);
+            *node = next_state;
+            carrier = carrier.mv();
+        }
+
+        Ok(infections)
+    }
+}
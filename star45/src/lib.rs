@@ -0,0 +1,47 @@
+#[macro_use]
+extern crate aoc;
+
+use aoc::Error;
+use aoc::Solution;
+use aoc::duet::Instruction;
+use aoc::duet::InstructionSet;
+use aoc::duet::Opcode;
+use aoc::duet::Vm;
+
+// Registers a and h
+const A: usize = 0;
+const H: usize = 7;
+
+// Runs the program on the coprocessor with register a set first. Counting
+// the composites among a thousand numbers takes far too long one instruction
+// at a time with a = 1, so the loops testing them are fused.
+fn run(prog: &[Instruction], a: i64) -> Result<Vm<()>, Error> {
+    let set = InstructionSet::coprocessor();
+    let mut vm = Vm::new(&set, prog, ());
+    vm.set_reg(A, a);
+    let fused = vm.optimize();
+    debug!("Fused {} loops", fused);
+    vm.run()?;
+    debug!("a: {} registers: {:?} after {} steps", a, vm.registers(), vm.steps());
+    Ok(vm)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Vec<Instruction>;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, Error> {
+        InstructionSet::coprocessor().parse(input)
+    }
+
+    fn part1(prog: &Vec<Instruction>) -> Result<usize, Error> {
+        Ok(run(prog, 0)?.count(Opcode::Mul))
+    }
+
+    fn part2(prog: &Vec<Instruction>) -> Result<i64, Error> {
+        Ok(run(prog, 1)?.reg(H))
+    }
+}
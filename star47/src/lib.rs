@@ -0,0 +1,133 @@
+#[macro_use]
+extern crate aoc;
+
+use std::fmt;
+use std::cmp;
+use std::str::FromStr;
+use aoc::Error;
+use aoc::Solution;
+
+type Port = u32;
+
+#[derive(Copy,Clone,Eq,PartialEq,Default)]
+pub struct Component (Port, Port);
+
+impl Component {
+    //fn with_ports(from: Port, to: Port) -> Component {
+    //    Component(from, to)
+    //}
+
+    fn low(&self) -> Port {
+        cmp::min(self.0, self.1)
+    }
+    fn high(&self) -> Port {
+        cmp::max(self.0, self.1)
+    }
+    fn strength(&self) -> u32 {
+        self.0 + self.1
+    }
+    fn can_connect(&self, other: Port) -> bool {
+        self.0 == other || self.1 == other
+    }
+    fn get_other(&self, other: Port) -> Port {
+        if other == self.0 {
+            self.1
+        } else {
+            self.0
+        }
+    }
+}
+
+impl fmt::Debug for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.0, self.1)
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.0, self.1)
+    }
+}
+
+impl FromStr for Component {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let p: Vec<Port> = s.split('/').map(|p| aoc::parse_token(s, p.trim())).collect::<Result<_,_>>()?;
+        if p.len() != 2 {
+            return Err(Error::token(s, s.trim(), "Expected <port>/<port>"));
+        }
+        Ok(Component(
+                cmp::min(p[0], p[1]),
+                cmp::max(p[0], p[1]),
+                ))
+    }
+}
+
+impl PartialOrd for Component {
+    fn partial_cmp(&self, other: &Component) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Component {
+    fn cmp(&self, other: &Component) -> cmp::Ordering {
+        cmp::Ordering::Equal
+            .then(self.strength().cmp(&other.strength()))
+            .then(self.low().cmp(&other.low()))
+            .then(self.high().cmp(&other.high()))
+    }
+}
+
+fn bridge_strength(bridge: &[Component]) -> u32 {
+    bridge.iter().map(Component::strength).sum()
+}
+
+// Bridges are ranked by the key function, the best bridge has the largest key
+fn build_bridge<F, K>(components: &[Component], bridge: &[Component], connector: Port, key: &F) -> (Vec<Component>, u32)
+    where F: Fn(&[Component], u32) -> K,
+          K: Ord,
+{
+    let mut local = bridge.to_vec();
+    let mut best = bridge.to_vec();
+    let mut best_strength = bridge_strength(bridge);
+    for candidate in components.iter().filter(|c| c.can_connect(connector)).filter(|c| !bridge.contains(c)) {
+        local.push(*candidate);
+        let (b, s) = build_bridge(components, &local[..], candidate.get_other(connector), key);
+        local.pop();
+        if key(&b, s) > key(&best, best_strength) {
+            best = b;
+            best_strength = s;
+        }
+    }
+    if local.len() < 5 {
+        debug!("{:4} {:?}", best_strength, best);
+    }
+    (best, best_strength)
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Component>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Component>, Error> {
+        let mut v: Vec<Component> = aoc::parse_lines(input)?;
+        v.sort();
+        v.reverse();
+        Ok(v)
+    }
+
+    fn part1(v: &Vec<Component>) -> Result<u32, Error> {
+        let (b, s) = build_bridge(v, &[Component(0,0)], 0, &|_, s| s);
+        info!("Strongest bridge: {:4} {:?}", s, b);
+        Ok(s)
+    }
+
+    fn part2(v: &Vec<Component>) -> Result<u32, Error> {
+        let (b, s) = build_bridge(v, &[Component(0,0)], 0, &|b, s| (b.len(), s));
+        info!("Longest bridge: {:4} {} {:?}", s, b.len()-1, b);
+        Ok(s)
+    }
+}
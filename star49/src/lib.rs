@@ -0,0 +1,243 @@
+#[macro_use]
+extern crate aoc;
+
+use std::fmt;
+use std::str::FromStr;
+use std::collections::HashMap;
+use aoc::Error;
+use aoc::Solution;
+
+type CellType = bool;
+type State = char;
+
+#[derive(Copy,Clone,Debug,PartialEq,Eq)]
+enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Copy,Clone,Debug,PartialEq,Eq)]
+struct Effect {
+    value: CellType,
+    dir: Direction,
+    state: State,
+}
+
+impl Effect {
+    fn with(value: CellType, dir: Direction, state: State) -> Effect {
+        Effect{value, dir, state}
+    }
+}
+
+struct Turing {
+    tape_left: Vec<CellType>,
+    tape_right: Vec<CellType>,
+    transitions: HashMap<(State, CellType), Effect>,
+    state: State,
+    value: CellType,
+}
+
+impl fmt::Display for Turing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "State: {}", self.state)?;
+        
+
+        for left in self.tape_left.iter() {
+            write!(f, " {} ", *left as u8)?;
+        }
+
+        write!(f, "[{}]", if self.value { "1" } else { "0" })?;
+
+        for right in self.tape_right.iter().rev() {
+            write!(f, " {} ", *right as u8)?;
+        }
+        Ok(())
+    }
+}
+
+impl Turing {
+    fn new() -> Turing {
+        Turing {
+            tape_left: Vec::new(),
+            tape_right: Vec::new(),
+            transitions: HashMap::new(),
+            state: '\0',
+            value: false,
+        }
+    }
+
+    fn with_start(state: State) -> Turing {
+        Turing {
+            state,
+            .. Turing::new()
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        match self.get_effect() {
+            None => false,
+            Some(e) => {
+                self.apply_effect(e);
+                true
+            }
+        }
+    }
+
+    fn get_effect(&self) -> Option<Effect> {
+        self.transitions.get(&(self.state, self.value)).cloned()
+    }
+
+    fn mv(&mut self, dir: Direction) {
+        match dir {
+            Direction::Left => {
+                self.tape_right.push(self.value);
+                self.value = self.tape_left.pop().unwrap_or_default();
+            }
+            Direction::Right => {
+                self.tape_left.push(self.value);
+                self.value = self.tape_right.pop().unwrap_or_default();
+            }
+        }
+    }
+
+    fn apply_effect(&mut self, effect: Effect) {
+        self.value = effect.value;
+        self.mv(effect.dir);
+        self.state = effect.state;
+    }
+
+    fn checksum(&self) -> usize {
+        self.value as usize
+            + self.tape_left.iter().map(|&c| c as usize).sum::<usize>()
+            + self.tape_right.iter().map(|&c| c as usize).sum::<usize>()
+    }
+}
+
+pub struct Blueprint {
+    start: State,
+    steps: usize,
+    transitions: HashMap<(State, CellType), Effect>,
+}
+
+// The non-blank lines of a blueprint, each of which has a fixed text around
+// a single field
+struct Lines<'a> {
+    lines: Vec<(usize, &'a str)>,
+    pos: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn done(&self) -> bool {
+        self.pos == self.lines.len()
+    }
+
+    fn expect<T, F>(&mut self, prefix: &str, suffix: &str, parse: F) -> Result<T, Error>
+        where F: FnOnce(&'a str, &'a str) -> Result<T, Error>
+    {
+        let (n, line) = *self.lines.get(self.pos)
+            .ok_or_else(|| Error::from(format!("Expected \"{}...{}\" at end of blueprint", prefix, suffix)))?;
+        self.pos += 1;
+
+        let s = line.trim();
+        if s.len() < prefix.len() + suffix.len() || !s.starts_with(prefix) || !s.ends_with(suffix) {
+            let e = Error::token(line, s, format!("Expected \"{}...{}\"", prefix, suffix));
+            return Err(e.at_line(n));
+        }
+        parse(line, &s[prefix.len()..s.len() - suffix.len()]).map_err(|e| e.at_line(n))
+    }
+}
+
+fn parse_state(line: &str, s: &str) -> Result<State, Error> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(Error::token(line, s, "Invalid state")),
+    }
+}
+
+fn parse_value(line: &str, s: &str) -> Result<CellType, Error> {
+    match s {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(Error::token(line, s, "Invalid value")),
+    }
+}
+
+fn parse_direction(line: &str, s: &str) -> Result<Direction, Error> {
+    match s {
+        "left" => Ok(Direction::Left),
+        "right" => Ok(Direction::Right),
+        _ => Err(Error::token(line, s, "Invalid direction")),
+    }
+}
+
+impl FromStr for Blueprint {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Blueprint, Error> {
+        let mut lines = Lines {
+            lines: s.lines()
+                .enumerate()
+                .map(|(i, l)| (i + 1, l))
+                .filter(|&(_, l)| !l.trim().is_empty())
+                .collect(),
+            pos: 0,
+        };
+
+        let start = lines.expect("Begin in state ", ".", parse_state)?;
+        let steps = lines.expect("Perform a diagnostic checksum after ", " steps.", aoc::parse_token)?;
+
+        let mut transitions = HashMap::new();
+        while !lines.done() {
+            let state = lines.expect("In state ", ":", parse_state)?;
+            for _ in 0..2 {
+                let rule = lines.pos;
+                let value = lines.expect("If the current value is ", ":", parse_value)?;
+                let effect = Effect::with(
+                    lines.expect("- Write the value ", ".", parse_value)?,
+                    lines.expect("- Move one slot to the ", ".", parse_direction)?,
+                    lines.expect("- Continue with state ", ".", parse_state)?);
+                if transitions.insert((state, value), effect).is_some() {
+                    let (n, line) = lines.lines[rule];
+                    let e = Error::token(line, line.trim(), format!("Duplicate rule for state {}", state));
+                    return Err(e.at_line(n));
+                }
+            }
+        }
+
+        Ok(Blueprint{start, steps, transitions})
+    }
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Blueprint;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Blueprint, Error> {
+        input.parse()
+    }
+
+    fn part1(blueprint: &Blueprint) -> Result<usize, Error> {
+        let mut tm = Turing::with_start(blueprint.start);
+        tm.transitions = blueprint.transitions.clone();
+
+        for i in 0..blueprint.steps {
+            if i % 1_000_000 == 0 {
+                trace!("{}", tm);
+            }
+            if !tm.step() {
+                return Err(Error::Solve(format!("No rule for state {} and value {}",
+                                                tm.state, tm.value as u8)));
+            }
+        }
+
+        trace!("{}", tm);
+        Ok(tm.checksum())
+    }
+
+    fn part2(_: &Blueprint) -> Result<usize, Error> {
+        Err(Error::Solve(String::from("Day 25 has no second part")))
+    }
+}